@@ -0,0 +1,192 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::rule::Rule;
+use crate::topology::Topology;
+
+/// Storage for the cell states
+enum Backend<C> {
+    /// Only live cells are stored - suitable for unbounded topologies
+    Sparse(HashSet<C>),
+    /// Every cell is stored with precomputed neighbour indices - requires a finite topology
+    Dense {
+        cells: Vec<C>,
+        index: HashMap<C, usize>,
+        neighbours: Vec<Vec<usize>>,
+        state: Vec<bool>,
+        next: Vec<bool>,
+    },
+}
+
+/// Cellular automaton which applies a rule to every cell of a topology simultaneously
+pub struct CellularAutomaton<T: Topology> {
+    topology: T,
+    rule: Rule,
+    backend: Backend<T::Cell>,
+    generation: usize,
+}
+
+impl<T: Topology> CellularAutomaton<T> {
+    /// Create an automaton storing only the live cells.
+    /// Only cells with at least one live neighbour are considered, so the rule must not give birth to a cell with 0 live neighbours.
+    ///
+    /// # Arguments
+    ///
+    /// * `topology` the topology to simulate on
+    /// * `rule` the birth/survival rule
+    /// * `live` the initially live cells
+    pub fn sparse<I>(topology: T, rule: Rule, live: I) -> Self
+    where
+        I: IntoIterator<Item = T::Cell>,
+    {
+        CellularAutomaton {
+            topology,
+            rule,
+            backend: Backend::Sparse(live.into_iter().collect()),
+            generation: 0,
+        }
+    }
+
+    /// Create an automaton storing every cell with a precomputed table of neighbours
+    ///
+    /// # Arguments
+    ///
+    /// * `topology` the topology to simulate on (must be finite)
+    /// * `rule` the birth/survival rule
+    /// * `live` the initially live cells (cells not in the topology are ignored)
+    ///
+    /// # Panics
+    ///
+    /// * If the topology does not have a finite list of cells
+    pub fn dense<I>(topology: T, rule: Rule, live: I) -> Self
+    where
+        I: IntoIterator<Item = T::Cell>,
+    {
+        let cells = topology.cells().expect("Dense backend requires a finite topology");
+        let index: HashMap<T::Cell, usize> = cells.iter().enumerate().map(|(i, c)| (*c, i)).collect();
+        let neighbours = cells
+            .iter()
+            .map(|c| topology.neighbours(*c).iter().filter_map(|n| index.get(n).copied()).collect())
+            .collect();
+        let mut state = vec!(false; cells.len());
+        for cell in live {
+            if let Some(i) = index.get(&cell) {
+                state[*i] = true;
+            }
+        }
+        let next = state.clone();
+        CellularAutomaton {
+            topology,
+            rule,
+            backend: Backend::Dense { cells, index, neighbours, state, next },
+            generation: 0,
+        }
+    }
+
+    /// Advance the automaton by one generation
+    ///
+    /// # Returns
+    ///
+    /// * The number of cells that changed state
+    pub fn step(&mut self) -> usize {
+        self.generation += 1;
+        match &mut self.backend {
+            Backend::Sparse(live) => {
+                // Tally live neighbours for every cell next to a live cell
+                let mut counts: HashMap<T::Cell, usize> = live.iter().map(|c| (*c, 0)).collect();
+                for cell in live.iter() {
+                    for n in self.topology.neighbours(*cell) {
+                        *counts.entry(n).or_insert(0) += 1;
+                    }
+                }
+                let mut changes = 0;
+                let mut next = HashSet::with_capacity(live.len());
+                for (cell, count) in counts {
+                    let alive = live.contains(&cell);
+                    let now_alive = self.rule.next_state(alive, count);
+                    if now_alive {
+                        next.insert(cell);
+                    }
+                    if now_alive != alive {
+                        changes += 1;
+                    }
+                }
+                *live = next;
+                changes
+            }
+            Backend::Dense { neighbours, state, next, .. } => {
+                let mut changes = 0;
+                for (i, adjacent) in neighbours.iter().enumerate() {
+                    let count = adjacent.iter().filter(|n| state[**n]).count();
+                    next[i] = self.rule.next_state(state[i], count);
+                    if next[i] != state[i] {
+                        changes += 1;
+                    }
+                }
+                std::mem::swap(state, next);
+                changes
+            }
+        }
+    }
+
+    /// Advance the automaton by the given number of generations
+    pub fn run(&mut self, generations: usize) {
+        for _ in 0..generations {
+            self.step();
+        }
+    }
+
+    /// Advance the automaton until no cells change state
+    ///
+    /// # Arguments
+    ///
+    /// * `max_generations` the maximum number of generations to simulate
+    ///
+    /// # Returns
+    ///
+    /// * The number of generations that changed at least one cell, or None if still changing after `max_generations`
+    pub fn run_until_stable(&mut self, max_generations: usize) -> Option<usize> {
+        let start = self.generation;
+        for _ in 0..max_generations {
+            if self.step() == 0 {
+                return Some(self.generation - start - 1);
+            }
+        }
+        None
+    }
+
+    /// Number of generations simulated so far
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Number of live cells
+    pub fn live_count(&self) -> usize {
+        match &self.backend {
+            Backend::Sparse(live) => live.len(),
+            Backend::Dense { state, .. } => state.iter().filter(|s| **s).count(),
+        }
+    }
+
+    /// Check whether the given cell is alive
+    pub fn is_alive(&self, cell: T::Cell) -> bool {
+        match &self.backend {
+            Backend::Sparse(live) => live.contains(&cell),
+            Backend::Dense { index, state, .. } => index.get(&cell).map(|i| state[*i]).unwrap_or(false),
+        }
+    }
+
+    /// List all live cells
+    pub fn live_cells(&self) -> Vec<T::Cell> {
+        match &self.backend {
+            Backend::Sparse(live) => live.iter().copied().collect(),
+            Backend::Dense { cells, state, .. } => {
+                cells.iter().zip(state.iter()).filter(|(_, s)| **s).map(|(c, _)| *c).collect()
+            }
+        }
+    }
+
+    /// The topology being simulated
+    pub fn topology(&self) -> &T {
+        &self.topology
+    }
+}
@@ -0,0 +1,8 @@
+pub mod topology;
+pub use topology::*;
+
+pub mod rule;
+pub use rule::*;
+
+pub mod engine;
+pub use engine::*;
@@ -0,0 +1,93 @@
+use std::collections::HashSet;
+use std::fmt;
+
+/// Error returned when a rule in B/S notation can't be interpreted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleError(pub String);
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid rule: {}", self.0)
+    }
+}
+
+impl std::error::Error for RuleError {}
+
+/// Decides whether a cell is alive in the next generation given its current state and live neighbour count
+pub struct Rule {
+    next: Box<dyn Fn(bool, usize) -> bool>,
+}
+
+impl Rule {
+    /// Create a rule from a closure taking (alive, live neighbour count) and returning the next state
+    pub fn from_fn<F>(f: F) -> Self
+    where
+        F: Fn(bool, usize) -> bool + 'static,
+    {
+        Rule { next: Box::new(f) }
+    }
+
+    /// Create a rule from the neighbour counts that cause a dead cell to be born or a live cell to survive
+    ///
+    /// # Arguments
+    ///
+    /// * `birth` counts for which a dead cell becomes alive
+    /// * `survival` counts for which a live cell stays alive
+    pub fn birth_survival(birth: &[usize], survival: &[usize]) -> Self {
+        let birth: HashSet<usize> = birth.iter().copied().collect();
+        let survival: HashSet<usize> = survival.iter().copied().collect();
+        Rule::from_fn(move |alive, count| {
+            if alive {
+                survival.contains(&count)
+            } else {
+                birth.contains(&count)
+            }
+        })
+    }
+
+    /// Parse a rule in B/S notation, e.g. "B3/S23" for Conway's Game of Life.
+    /// Counts are single digits unless separated by commas (e.g. "B3/S2,3,12").
+    ///
+    /// # Arguments
+    ///
+    /// * `notation` the rule string
+    pub fn parse(notation: &str) -> Result<Self, RuleError> {
+        fn parse_counts(s: &str) -> Result<Vec<usize>, RuleError> {
+            if s.contains(',') {
+                s.split(',')
+                    .filter(|x| !x.is_empty())
+                    .map(|x| x.trim().parse().map_err(|_| RuleError(format!("bad count \"{}\"", x))))
+                    .collect()
+            } else {
+                s.chars()
+                    .map(|ch| ch.to_digit(10).map(|d| d as usize).ok_or_else(|| RuleError(format!("bad count '{}'", ch))))
+                    .collect()
+            }
+        }
+
+        let mut birth = None;
+        let mut survival = None;
+        for part in notation.trim().split('/') {
+            let part = part.trim();
+            match part.chars().next() {
+                Some('B') | Some('b') if birth.is_none() => birth = Some(parse_counts(&part[1..])?),
+                Some('S') | Some('s') if survival.is_none() => survival = Some(parse_counts(&part[1..])?),
+                _ => return Err(RuleError(format!("unexpected section \"{}\" in \"{}\"", part, notation))),
+            }
+        }
+        match (birth, survival) {
+            (Some(b), Some(s)) => Ok(Rule::birth_survival(&b, &s)),
+            _ => Err(RuleError(format!("\"{}\" needs both a B and an S section", notation))),
+        }
+    }
+
+    /// Return whether a cell is alive in the next generation
+    ///
+    /// # Arguments
+    ///
+    /// * `alive` whether the cell is currently alive
+    /// * `count` the number of live neighbours
+    pub fn next_state(&self, alive: bool, count: usize) -> bool {
+        (self.next)(alive, count)
+    }
+}
@@ -0,0 +1,144 @@
+use std::hash::Hash;
+use std::ops::Range;
+
+/// Describes which cells exist and which cells neighbour each other
+pub trait Topology {
+    type Cell: Copy + Eq + Hash;
+
+    /// Return the cells adjacent to the given cell
+    fn neighbours(&self, cell: Self::Cell) -> Vec<Self::Cell>;
+
+    /// Return every cell if the topology is finite (required for the dense backend)
+    fn cells(&self) -> Option<Vec<Self::Cell>> {
+        None
+    }
+}
+
+/// Which cells count as neighbours on a square grid
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// The (up to) eight cells immediately surrounding a cell
+    Adjacent,
+    /// The first cell visible in each of the eight directions (skipping positions with no cell)
+    LineOfSight,
+}
+
+/// Finite square grid where only some positions hold cells (e.g. seats rather than floor)
+/// Cells are (row, column) pairs
+#[derive(Debug, Clone)]
+pub struct SquareGrid {
+    mask: Vec<Vec<bool>>,
+    neighbourhood: Neighbourhood,
+}
+
+impl SquareGrid {
+    /// Create a new square grid
+    ///
+    /// # Arguments
+    ///
+    /// * `mask` for each row and column whether the position holds a cell
+    /// * `neighbourhood` whether neighbours are adjacent cells or cells in line of sight
+    pub fn new(mask: Vec<Vec<bool>>, neighbourhood: Neighbourhood) -> Self {
+        SquareGrid { mask, neighbourhood }
+    }
+
+    /// Check whether a position is on the grid and holds a cell
+    fn is_cell(&self, r: i32, c: i32) -> bool {
+        r >= 0 && c >= 0
+            && self.mask.get(r as usize).and_then(|row| row.get(c as usize)).copied().unwrap_or(false)
+    }
+
+    /// Check whether a position is on the grid at all
+    fn in_bounds(&self, r: i32, c: i32) -> bool {
+        r >= 0 && c >= 0 && (r as usize) < self.mask.len() && (c as usize) < self.mask[r as usize].len()
+    }
+}
+
+impl Topology for SquareGrid {
+    type Cell = (i32, i32);
+
+    fn neighbours(&self, cell: (i32, i32)) -> Vec<(i32, i32)> {
+        let mut result = Vec::new();
+        for dr in -1..=1 {
+            for dc in -1..=1 {
+                if dr == 0 && dc == 0 {
+                    continue;
+                }
+                let (mut r, mut c) = (cell.0 + dr, cell.1 + dc);
+                match self.neighbourhood {
+                    Neighbourhood::Adjacent => {
+                        if self.is_cell(r, c) {
+                            result.push((r, c));
+                        }
+                    }
+                    Neighbourhood::LineOfSight => {
+                        while self.in_bounds(r, c) {
+                            if self.is_cell(r, c) {
+                                result.push((r, c));
+                                break;
+                            }
+                            r += dr;
+                            c += dc;
+                        }
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    fn cells(&self) -> Option<Vec<(i32, i32)>> {
+        let mut cells = Vec::new();
+        for (r, row) in self.mask.iter().enumerate() {
+            for (c, is_cell) in row.iter().enumerate() {
+                if *is_cell {
+                    cells.push((r as i32, c as i32));
+                }
+            }
+        }
+        Some(cells)
+    }
+}
+
+/// Infinite D-dimensional lattice where each point has 3^D - 1 neighbours
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Lattice<const D: usize>;
+
+impl<const D: usize> Lattice<D> {
+    /// Every point within one step of a point along some of the axes (including the point itself)
+    ///
+    /// # Arguments
+    ///
+    /// * `cell` the point to look around
+    /// * `axes` the axes which may be stepped along - the others keep the point's coordinate
+    pub fn around(cell: [i32; D], axes: Range<usize>) -> Vec<[i32; D]> {
+        let mut result = Vec::with_capacity(3usize.pow(axes.len() as u32));
+        let mut p = cell;
+        for i in axes.clone() {
+            p[i] -= 1;
+        }
+        loop {
+            result.push(p);
+            // Step the point through -1..=1 of the cell along each axis like an odometer
+            let mut i = axes.start;
+            while i < axes.end && p[i] == cell[i] + 1 {
+                p[i] = cell[i] - 1;
+                i += 1;
+            }
+            if i == axes.end {
+                return result;
+            }
+            p[i] += 1;
+        }
+    }
+}
+
+impl<const D: usize> Topology for Lattice<D> {
+    type Cell = [i32; D];
+
+    fn neighbours(&self, cell: [i32; D]) -> Vec<[i32; D]> {
+        let mut result = Lattice::around(cell, 0..D);
+        result.retain(|p| *p != cell);
+        result
+    }
+}
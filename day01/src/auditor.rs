@@ -0,0 +1,150 @@
+use std::collections::{HashMap, HashSet};
+
+/// A combination of entries found to sum to the target
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Finding {
+    Pair(i64, i64),
+    Triple(i64, i64, i64),
+}
+
+/// Incremental expense report auditor which looks for a pair and a triple of entries summing to a target.
+///
+/// Entries are added one at a time and checked against a hash index of the earlier entries (for pairs)
+/// and of the sums of every pair of earlier entries (for triples), so a finding is reported as soon as
+/// the entry completing it arrives.
+#[derive(Debug, Clone)]
+pub struct ExpenseAuditor {
+    target: i64,
+    entries: HashSet<i64>,
+    pair_sums: HashMap<i64, (i64, i64)>,
+    memory_limit: Option<usize>,
+    pair: Option<(i64, i64)>,
+    triple: Option<(i64, i64, i64)>,
+    seen: usize,
+}
+
+impl ExpenseAuditor {
+    /// Create a new auditor
+    ///
+    /// # Arguments
+    ///
+    /// * `target` the value the pair and triple must sum to
+    pub fn new(target: i64) -> Self {
+        ExpenseAuditor {
+            target,
+            entries: HashSet::new(),
+            pair_sums: HashMap::new(),
+            memory_limit: None,
+            pair: None,
+            triple: None,
+            seen: 0,
+        }
+    }
+
+    /// Limit the number of values (entries plus pair sums) held in the index.
+    /// Once the limit is reached pair sums are dropped to make room for new entries, and once
+    /// only entries remain new entries are still checked but no longer indexed - so findings
+    /// relying on dropped values can be missed.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` the maximum number of indexed values
+    pub fn with_memory_limit(mut self, limit: usize) -> Self {
+        self.memory_limit = Some(limit);
+        self
+    }
+
+    /// Check whether another value can be indexed without exceeding the memory limit
+    ///
+    /// # Arguments
+    ///
+    /// * `reserved` the number of slots already promised to other values
+    fn has_capacity(&self, reserved: usize) -> bool {
+        match self.memory_limit {
+            Some(limit) => self.indexed() + reserved < limit,
+            None => true,
+        }
+    }
+
+    /// Add an entry to the audit
+    ///
+    /// # Arguments
+    ///
+    /// * `entry` the next expense report entry
+    ///
+    /// # Returns
+    ///
+    /// * Any findings that were completed by this entry
+    pub fn add(&mut self, entry: i64) -> Vec<Finding> {
+        self.seen += 1;
+        let mut findings = Vec::new();
+        // No other entries can make up the difference if it's out of range
+        if let Some(remainder) = self.target.checked_sub(entry) {
+            if self.pair.is_none() && self.entries.contains(&remainder) {
+                self.pair = Some((remainder, entry));
+                findings.push(Finding::Pair(remainder, entry));
+            }
+            if self.triple.is_none() {
+                if let Some((a, b)) = self.pair_sums.get(&remainder) {
+                    self.triple = Some((*a, *b, entry));
+                    findings.push(Finding::Triple(*a, *b, entry));
+                }
+            }
+        }
+        if self.is_complete() {
+            // Nothing else to look for - release the index
+            self.entries.clear();
+            self.pair_sums.clear();
+            return findings;
+        }
+
+        // The entry itself takes priority over pair sums when the index is full
+        if !self.has_capacity(0) {
+            if let Some(sum) = self.pair_sums.keys().next().copied() {
+                self.pair_sums.remove(&sum);
+            }
+        }
+        let index_entry = self.has_capacity(0);
+        // Pair sums are only needed while still looking for a triple
+        if self.triple.is_none() {
+            let reserved = if index_entry { 1 } else { 0 };
+            for other in self.entries.iter() {
+                if !self.has_capacity(reserved) {
+                    break;
+                }
+                if let Some(sum) = other.checked_add(entry) {
+                    self.pair_sums.entry(sum).or_insert((*other, entry));
+                }
+            }
+        }
+        if index_entry {
+            self.entries.insert(entry);
+        }
+        findings
+    }
+
+    /// The pair of entries summing to the target (if found)
+    pub fn pair(&self) -> Option<(i64, i64)> {
+        self.pair
+    }
+
+    /// The triple of entries summing to the target (if found)
+    pub fn triple(&self) -> Option<(i64, i64, i64)> {
+        self.triple
+    }
+
+    /// Whether both the pair and the triple have been found
+    pub fn is_complete(&self) -> bool {
+        self.pair.is_some() && self.triple.is_some()
+    }
+
+    /// Number of entries added so far
+    pub fn entries_seen(&self) -> usize {
+        self.seen
+    }
+
+    /// Number of values currently held in the index
+    pub fn indexed(&self) -> usize {
+        self.entries.len() + self.pair_sums.len()
+    }
+}
@@ -0,0 +1,155 @@
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// Integer types which can be searched for k-sums.
+///
+/// Sums are worked out in an i128 accumulator rather than in the type itself, so a solution is never missed
+/// because a partial sum or remainder doesn't fit in the type. Only types of up to 64 bits are supported
+/// since the sum of any number of them (below 2^64) fits in an i128 exactly.
+pub trait Integer: Copy + Ord + Hash + Debug {
+    fn to_i128(self) -> i128;
+    fn from_i128(n: i128) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+
+                fn from_i128(n: i128) -> Option<Self> {
+                    <$t>::try_from(n).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// Find k entries (at different positions) in a list of values that add up to the target
+/// Uses two pointers for k = 2 and recursion beyond that, pruning entries which can't reach the target
+///
+/// # Arguments
+///
+/// * `values` the values to search (don't need to be sorted)
+/// * `k` the number of entries to use
+/// * `target` the value the entries must sum to
+///
+/// # Returns
+///
+/// * The entries (smallest to largest) or None if there is no solution
+pub fn k_sum<T: Integer>(values: &[T], k: usize, target: T) -> Option<Vec<T>> {
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+    let mut solutions = Vec::new();
+    search(&sorted, k, target.to_i128(), &mut Vec::new(), &mut solutions, true);
+    solutions.pop()
+}
+
+/// Find every distinct combination of k entries (at different positions) that add up to the target
+///
+/// # Arguments
+///
+/// * `values` the values to search (don't need to be sorted)
+/// * `k` the number of entries to use
+/// * `target` the value the entries must sum to
+///
+/// # Returns
+///
+/// * Each combination of entries (smallest to largest) - combinations are listed in ascending order
+pub fn k_sum_all<T: Integer>(values: &[T], k: usize, target: T) -> Vec<Vec<T>> {
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+    let mut solutions = Vec::new();
+    search(&sorted, k, target.to_i128(), &mut Vec::new(), &mut solutions, false);
+    solutions
+}
+
+/// Sum the given values in the i128 accumulator
+fn wide_sum<T: Integer>(values: &[T]) -> i128 {
+    values.iter().map(|x| x.to_i128()).sum()
+}
+
+/// Recursively fix the smallest entry and search the rest of the (sorted) values for a (k-1)-sum
+///
+/// # Arguments
+///
+/// * `sorted` the values to search - sorted smallest to largest
+/// * `k` the number of entries still to choose
+/// * `target` the value the remaining entries must sum to (which may be outside the range of `T`)
+/// * `chosen` the entries chosen so far
+/// * `solutions` list to add any solutions to
+/// * `first_only` stop after the first solution is found
+fn search<T: Integer>(sorted: &[T], k: usize, target: i128, chosen: &mut Vec<T>, solutions: &mut Vec<Vec<T>>, first_only: bool) {
+    if k > sorted.len() {
+        return;
+    }
+    match k {
+        0 => {
+            if target == 0 {
+                solutions.push(chosen.clone());
+            }
+        }
+        1 => {
+            if let Some(last) = T::from_i128(target).filter(|t| sorted.binary_search(t).is_ok()) {
+                let mut solution = chosen.clone();
+                solution.push(last);
+                solutions.push(solution);
+            }
+        }
+        2 => {
+            // Two pointers working inwards from either end
+            let (mut i, mut j) = (0, sorted.len() - 1);
+            while i < j {
+                let sum = sorted[i].to_i128() + sorted[j].to_i128();
+                match sum.cmp(&target) {
+                    Ordering::Equal => {
+                        let mut solution = chosen.clone();
+                        solution.push(sorted[i]);
+                        solution.push(sorted[j]);
+                        solutions.push(solution);
+                        if first_only {
+                            return;
+                        }
+                        // Skip over repeated values so each combination is only listed once
+                        let (x, y) = (sorted[i], sorted[j]);
+                        while i < j && sorted[i] == x {
+                            i += 1;
+                        }
+                        while i < j && sorted[j] == y {
+                            j -= 1;
+                        }
+                    }
+                    Ordering::Less => i += 1,
+                    Ordering::Greater => j -= 1,
+                }
+            }
+        }
+        _ => {
+            for i in 0..=sorted.len() - k {
+                if i > 0 && sorted[i] == sorted[i - 1] {
+                    continue;
+                }
+                // Smallest possible sum using this entry - since values are sorted nothing later can be smaller
+                if wide_sum(&sorted[i..i + k]) > target {
+                    break;
+                }
+                // Largest possible sum using this entry
+                if wide_sum(&sorted[sorted.len() - k + 1..]) + sorted[i].to_i128() < target {
+                    continue;
+                }
+                chosen.push(sorted[i]);
+                search(&sorted[i + 1..], k - 1, target - sorted[i].to_i128(), chosen, solutions, first_only);
+                chosen.pop();
+                if first_only && !solutions.is_empty() {
+                    return;
+                }
+            }
+        }
+    }
+}
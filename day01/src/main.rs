@@ -37,25 +37,51 @@
  */
 
 
+use std::env;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{self, BufRead, BufReader};
+
+mod ksum;
+use ksum::{k_sum, k_sum_all};
+
+mod auditor;
+use auditor::{ExpenseAuditor, Finding};
 
 fn main() {
+    // Large reports can be audited incrementally with `cargo run -- --stream <file or -> [memory limit]`
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Some(source) = args.first().filter(|a| *a == "--stream").and(args.get(1)) {
+        let limit = args.get(2).map(|l| l.parse().unwrap());
+        stream_audit(source, 2020, limit);
+        return;
+    }
+
     let filename = "src/input.txt";
     let lines = read_in_lines(filename);
 
     // Read the file line by line using the lines() iterator from std::io::BufRead.
     let mut values = Vec::new();
     for line in lines {
-        values.push(line.parse::<u32>().unwrap());
+        values.push(line.parse::<i64>().unwrap());
     }
-    values.sort();
 
-    let (x, y) = find_sum_from_two_values(2020, &values);
-    println!("{} * {} = {}", x, y, x * y);
+    print_k_sum(&values, 2, 2020);
+    print_k_sum(&values, 3, 2020);
 
-    let (x, y, z) = find_sum_from_three_values(2020, &values);
-    println!("{} * {} * {} = {}", x, y, z, x * y * z);
+    // Other audits can be run by passing the number of entries and the target, e.g. `cargo run -- 4 2020`
+    // Adding `--all` lists every combination rather than the first found
+    match args.as_slice() {
+        [k, target] => print_k_sum(&values, k.parse().unwrap(), target.parse().unwrap()),
+        [k, target, all] if all == "--all" => {
+            let solutions = k_sum_all(&values, k.parse().unwrap(), target.parse().unwrap());
+            for entries in &solutions {
+                print_entries(entries);
+            }
+            println!("{} combinations found", solutions.len());
+        }
+        [] => (),
+        _ => println!("Usage: day01 [k target [--all]] | day01 --stream <file or -> [memory limit]"),
+    }
 }
 
 /// Read in lines of a file to a vector
@@ -68,56 +94,81 @@ fn read_in_lines(filename: &str) -> Vec<String> {
     let file = File::open(filename).unwrap();
     let reader = BufReader::new(file);
 
-    reader.lines().flatten().collect()
+    reader.lines().map(|l| l.unwrap()).collect()
 }
 
 
-/// Find two values in the passed vector that add to the target value
-/// Note: Passed vector must be sorted smallest to largest
+/// Find k entries that sum to the target and print them along with their product
 /// 
 /// # Arguments
 /// 
+/// * `values` - The expense report entries
+/// * `k` - The number of entries to use
 /// * `target` - The target value
-/// * `sroted_values` - A sorted vector (smalles to largest) containing the search space
-fn find_sum_from_two_values(target: u32, sorted_values: &Vec<u32>) -> (u32, u32) {
-    // Iterate over all numbers and find the two that sum to target value
-    // We can break once sum is greater than target because numbers are
-    // ordered smallest to largest
-    for (i, x) in sorted_values[0..].iter().enumerate() {
-        if *x > target { break; }
-        for (_, y) in sorted_values[i..].iter().enumerate() {
-            if x + y  == target {
-                    return (*x, *y)
-            } else if x + y > target { break; }
-        }
+fn print_k_sum(values: &[i64], k: usize, target: i64) {
+    match k_sum(values, k, target) {
+        Some(entries) => print_entries(&entries),
+        None => println!("No {} entries sum to {}", k, target),
     }
-    (0, 0)
 }
 
 
-/// Find three values in the passed vector that add to the target value
-/// Note: Passed vector must be sorted smallest to largest
+/// Print entries multiplied together along with their product
 /// 
 /// # Arguments
 /// 
+/// * `entries` - The entries to print
+fn print_entries(entries: &[i64]) {
+    let terms: Vec<String> = entries.iter().map(|x| x.to_string()).collect();
+    let product: i128 = entries.iter().map(|x| *x as i128).product();
+    println!("{} = {}", terms.join(" * "), product);
+}
+
+
+/// Audit entries one line at a time from a file (or stdin if the source is "-"),
+/// printing the pair and triple as soon as they are found
+/// 
+/// # Arguments
+/// 
+/// * `source` - The filename to read or "-" for stdin
 /// * `target` - The target value
-/// * `sroted_values` - A sorted vector (smalles to largest) containing the search space
-fn find_sum_from_three_values(target: u32, sorted_values: &Vec<u32>) -> (u32, u32, u32) {
-    // Iterate over all numbers and find the two that sum to target value
-    // We can break once sum is greater than target because numbers are
-    // ordered smallest to largest
-    for (i, x) in sorted_values[0..].iter().enumerate() {
-        if *x > target { break; }
-        for (j, y) in sorted_values[i..].iter().enumerate() {
-            if x + y > target { break; } 
-            for (_, z) in sorted_values[j..].iter().enumerate() {
-                if x + y + z == target {
-                    return (*x, *y, *z)
-                } else if x + y + z > target { break; }
+/// * `memory_limit` - Optional maximum number of values to hold in the auditor's index
+fn stream_audit(source: &str, target: i64, memory_limit: Option<usize>) {
+    let reader: Box<dyn BufRead> = match source {
+        "-" => Box::new(BufReader::new(io::stdin())),
+        filename => Box::new(BufReader::new(File::open(filename).unwrap())),
+    };
+    let mut auditor = ExpenseAuditor::new(target);
+    if let Some(limit) = memory_limit {
+        auditor = auditor.with_memory_limit(limit);
+    }
+
+    for (i, line) in reader.lines().enumerate() {
+        let line = line.unwrap();
+        let entry = match line.trim().parse::<i64>() {
+            Ok(entry) => entry,
+            Err(_) => {
+                println!("Line {}: skipping \"{}\"", i + 1, line);
+                continue;
+            }
+        };
+        for finding in auditor.add(entry) {
+            match finding {
+                Finding::Pair(x, y) => println!("Line {}: {} * {} = {}", i + 1, x, y, x as i128 * y as i128),
+                Finding::Triple(x, y, z) =>
+                    println!("Line {}: {} * {} * {} = {}", i + 1, x, y, z, x as i128 * y as i128 * z as i128),
             }
         }
+        if auditor.is_complete() {
+            break;
+        }
     }
-    (0, 0, 0)
-}
-
 
+    if auditor.pair().is_none() {
+        println!("No pair sums to {}", target);
+    }
+    if auditor.triple().is_none() {
+        println!("No triple sums to {}", target);
+    }
+    println!("{} entries read, {} values indexed", auditor.entries_seen(), auditor.indexed());
+}
@@ -0,0 +1,199 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::policy::{Password, PasswordPolicy, Policy};
+
+/// Result of checking one line of the password database against every policy
+pub struct AuditEntry {
+    pub line: usize,
+    pub password: Password,
+    pub policy: PasswordPolicy,
+    /// One result per policy (in the order the policies were given) - Err holds the failure reason
+    pub results: Vec<Result<(), String>>,
+}
+
+/// A line of the password database which couldn't be parsed
+pub struct MalformedLine {
+    pub line: usize,
+    pub text: String,
+    pub reason: String,
+}
+
+/// Aggregated results for a single policy
+#[derive(Default)]
+pub struct PolicyStatistics {
+    pub passed: usize,
+    pub failed: usize,
+    /// Number of failures for each policy character
+    pub failures_by_character: BTreeMap<char, usize>,
+    /// Number of failures for each (first, second) range
+    pub failures_by_range: BTreeMap<(u32, u32), usize>,
+}
+
+/// Audit of a password database against a set of policies
+pub struct AuditReport {
+    pub policy_names: Vec<String>,
+    pub entries: Vec<AuditEntry>,
+    pub malformed: Vec<MalformedLine>,
+}
+
+impl AuditReport {
+    /// Check every parsed line of the database against every policy
+    ///
+    /// # Arguments
+    ///
+    /// * `records` each line number with its text and either the parsed password or the reason it couldn't be parsed
+    /// * `policies` the policies to check against
+    pub fn new<I>(records: I, policies: &[&dyn Policy]) -> Self
+    where
+        I: IntoIterator<Item = (usize, String, Result<(Password, PasswordPolicy), String>)>,
+    {
+        let mut entries = Vec::new();
+        let mut malformed = Vec::new();
+        for (line, text, record) in records {
+            match record {
+                Ok((password, policy)) => {
+                    let results = policies.iter().map(|rule| rule.check(&password, &policy)).collect();
+                    entries.push(AuditEntry { line, password, policy, results });
+                }
+                Err(reason) => malformed.push(MalformedLine { line, text, reason }),
+            }
+        }
+        AuditReport {
+            policy_names: policies.iter().map(|rule| rule.name().to_string()).collect(),
+            entries,
+            malformed,
+        }
+    }
+
+    /// Aggregate the results for each policy (in the order the policies were given)
+    pub fn statistics(&self) -> Vec<PolicyStatistics> {
+        let mut stats: Vec<PolicyStatistics> = self.policy_names.iter().map(|_| PolicyStatistics::default()).collect();
+        for entry in &self.entries {
+            for (s, result) in stats.iter_mut().zip(entry.results.iter()) {
+                match result {
+                    Ok(()) => s.passed += 1,
+                    Err(_) => {
+                        s.failed += 1;
+                        *s.failures_by_character.entry(entry.policy.character).or_insert(0) += 1;
+                        *s.failures_by_range.entry((entry.policy.first, entry.policy.second)).or_insert(0) += 1;
+                    }
+                }
+            }
+        }
+        stats
+    }
+
+    /// Format the report as text - a line per password followed by the statistics for each policy
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        for entry in &self.entries {
+            write!(out, "{}: {}-{} {}: {}", entry.line, entry.policy.first, entry.policy.second,
+                   entry.policy.character, entry.password.password).unwrap();
+            for (name, result) in self.policy_names.iter().zip(entry.results.iter()) {
+                match result {
+                    Ok(()) => write!(out, " | {}: pass", name).unwrap(),
+                    Err(reason) => write!(out, " | {}: FAIL ({})", name, reason).unwrap(),
+                }
+            }
+            out.push('\n');
+        }
+        for m in &self.malformed {
+            writeln!(out, "{}: MALFORMED \"{}\" ({})", m.line, m.text, m.reason).unwrap();
+        }
+
+        writeln!(out, "\n{} passwords checked, {} malformed lines", self.entries.len(), self.malformed.len()).unwrap();
+        for (name, s) in self.policy_names.iter().zip(self.statistics()) {
+            writeln!(out, "\n--- {} ---", name).unwrap();
+            writeln!(out, "Passed: {}", s.passed).unwrap();
+            writeln!(out, "Failed: {}", s.failed).unwrap();
+            let by_character: Vec<String> = s.failures_by_character.iter().map(|(c, n)| format!("{}={}", c, n)).collect();
+            writeln!(out, "Failures by character: {}", by_character.join(", ")).unwrap();
+            let by_range: Vec<String> = s.failures_by_range.iter().map(|((a, b), n)| format!("{}-{}={}", a, b, n)).collect();
+            writeln!(out, "Failures by range: {}", by_range.join(", ")).unwrap();
+        }
+        out
+    }
+
+    /// Format the report as CSV with one row per password and policy.
+    /// Malformed lines have an empty policy and a result of "malformed".
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("line,first,second,character,password,policy,result,reason\n");
+        for entry in &self.entries {
+            for (name, result) in self.policy_names.iter().zip(entry.results.iter()) {
+                let (status, reason) = match result {
+                    Ok(()) => ("pass", ""),
+                    Err(reason) => ("fail", reason.as_str()),
+                };
+                writeln!(out, "{},{},{},{},{},{},{},{}", entry.line, entry.policy.first, entry.policy.second,
+                         csv_field(&entry.policy.character.to_string()), csv_field(&entry.password.password),
+                         csv_field(name), status, csv_field(reason)).unwrap();
+            }
+        }
+        for m in &self.malformed {
+            writeln!(out, "{},,,,{},,malformed,{}", m.line, csv_field(&m.text), csv_field(&m.reason)).unwrap();
+        }
+        out
+    }
+
+    /// Format the report as a JSON object with the per-password results, malformed lines and statistics
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("{\n  \"passwords\": [");
+        for (i, entry) in self.entries.iter().enumerate() {
+            let results: Vec<String> = self.policy_names.iter().zip(entry.results.iter())
+                .map(|(name, result)| match result {
+                    Ok(()) => format!("{{\"policy\": {}, \"valid\": true}}", json_string(name)),
+                    Err(reason) => format!("{{\"policy\": {}, \"valid\": false, \"reason\": {}}}", json_string(name), json_string(reason)),
+                })
+                .collect();
+            write!(out, "{}\n    {{\"line\": {}, \"first\": {}, \"second\": {}, \"character\": {}, \"password\": {}, \"results\": [{}]}}",
+                   if i > 0 { "," } else { "" }, entry.line, entry.policy.first, entry.policy.second,
+                   json_string(&entry.policy.character.to_string()), json_string(&entry.password.password),
+                   results.join(", ")).unwrap();
+        }
+        out.push_str("\n  ],\n  \"malformed\": [");
+        for (i, m) in self.malformed.iter().enumerate() {
+            write!(out, "{}\n    {{\"line\": {}, \"text\": {}, \"reason\": {}}}", if i > 0 { "," } else { "" },
+                   m.line, json_string(&m.text), json_string(&m.reason)).unwrap();
+        }
+        out.push_str("\n  ],\n  \"statistics\": [");
+        for (i, (name, s)) in self.policy_names.iter().zip(self.statistics()).enumerate() {
+            let by_character: Vec<String> = s.failures_by_character.iter()
+                .map(|(c, n)| format!("{}: {}", json_string(&c.to_string()), n)).collect();
+            let by_range: Vec<String> = s.failures_by_range.iter()
+                .map(|((a, b), n)| format!("\"{}-{}\": {}", a, b, n)).collect();
+            write!(out, "{}\n    {{\"policy\": {}, \"passed\": {}, \"failed\": {}, \"failures_by_character\": {{{}}}, \"failures_by_range\": {{{}}}}}",
+                   if i > 0 { "," } else { "" }, json_string(name), s.passed, s.failed,
+                   by_character.join(", "), by_range.join(", ")).unwrap();
+        }
+        out.push_str("\n  ]\n}\n");
+        out
+    }
+}
+
+/// Quote a CSV field if it contains a comma, quote or newline
+fn csv_field(s: &str) -> String {
+    if s.contains(',') || s.contains('"') || s.contains('\n') {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Format a string as a quoted JSON string
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
@@ -1,4 +1,4 @@
-/**
+/*
 --- Day 2: Password Philosophy ---
 Your flight departs in a few days from the coastal airport; the easiest way down to the coast from here is via toboggan.
 The shopkeeper at the North Pole Toboggan Rental Shop is having a bad day. "Something's wrong with our computers; we can't log in!" You ask if you can take a look.
@@ -28,39 +28,64 @@ Given the same example list from above:
 How many passwords are valid according to the new interpretation of the policies?
 **/
 
+use std::env;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 
+mod policy;
+use policy::{Password, PasswordPolicy, Policy, SledRentalPolicy, TobogganCorpPolicy, load_policies};
+
+mod audit;
+use audit::AuditReport;
+
 fn main() {
 
-    let passwords = read_in_lines("src/passwords.txt");
+    let lines = read_in_lines("src/passwords.txt");
 
-    let mut part_one = 0;
-    let mut part_two = 0;
+    // Any additional policies written in the policy language
+    let extra_policies = match load_policies("src/policies.txt") {
+        Ok(policies) => policies,
+        Err(e) => {
+            println!("Unable to load policies - {}", e);
+            Vec::new()
+        }
+    };
 
-    for password in passwords {
-        let (p, policy) = parse_password(password);
-        if sled_rental_policy(&p, &policy) { part_one += 1; }
-        if toboggon_corp_policy(&p, &policy) { part_two += 1; }
+    // `cargo run -- audit [text|csv|json]` reports the result of every policy for every password
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.first().map(|a| a.as_str()) == Some("audit") {
+        let mut policies: Vec<&dyn Policy> = vec!(&SledRentalPolicy, &TobogganCorpPolicy);
+        policies.extend(extra_policies.iter().map(|rule| rule as &dyn Policy));
+        let records = lines.iter().enumerate().map(|(i, line)| (i + 1, line.clone(), parse_password(line)));
+        let report = AuditReport::new(records, &policies);
+        match args.get(1).map(|a| a.as_str()) {
+            None | Some("text") => print!("{}", report.to_text()),
+            Some("csv") => print!("{}", report.to_csv()),
+            Some("json") => print!("{}", report.to_json()),
+            Some(format) => println!("Unrecognised format \"{}\" (use text, csv or json)", format),
+        }
+        return;
     }
-    
-    println!("Part 1: {}", part_one);
-    println!("Part 2: {}", part_two);
-}
 
+    let mut passwords: Vec<(Password, PasswordPolicy)> = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        match parse_password(line) {
+            Ok(record) => passwords.push(record),
+            Err(reason) => println!("Skipping line {}: {}", i + 1, reason),
+        }
+    }
 
-/// Representation of a password policy
-struct PasswordPolicy {
-    character: char,
-    first: u32,
-    second: u32,
-}
+    let count_valid = |rule: &dyn Policy| passwords.iter().filter(|(p, policy)| rule.check(p, policy).is_ok()).count();
 
-/// Representation of a password
-struct Password {
-    password: String,
+    println!("Part 1: {}", count_valid(&SledRentalPolicy));
+    println!("Part 2: {}", count_valid(&TobogganCorpPolicy));
+
+    for rule in &extra_policies {
+        println!("{}: {} ({})", rule.name(), count_valid(rule), rule.expr);
+    }
 }
 
+
 /// Read in lines of a file to a vector
 /// 
 /// # Arguments
@@ -71,24 +96,32 @@ fn read_in_lines(filename: &str) -> Vec<String> {
     let file = File::open(filename).unwrap();
     let reader = BufReader::new(file);
 
-    reader.lines().flatten().collect()
+    reader.lines().map(|l| l.unwrap()).collect()
 }
 
 /// Parse a password file string into Password and PasswordPolicy
 /// 
 /// # Arguments
 /// 
-/// * `password` - String containing the password and policy
+/// * `password` - String containing the password and policy (e.g. "1-3 a: abcde")
 /// 
 /// # Return
 /// 
-/// * (Password, PasswordPolicy) as a tuple
-fn parse_password(password: String) -> (Password, PasswordPolicy) {
+/// * (Password, PasswordPolicy) as a tuple or the reason the line is malformed
+fn parse_password(password: &str) -> Result<(Password, PasswordPolicy), String> {
     let temp: Vec< &str > = password.split_whitespace().collect();
-    let range: Vec< &str > = temp[0].split('-').collect();
-    let first = range[0].parse::<u32>().unwrap();
-    let second = range[1].parse::<u32>().unwrap();
-    let character = temp[1].chars().nth(0).unwrap();
+    if temp.len() != 3 {
+        return Err(format!("expected \"<first>-<second> <char>: <password>\", found {} fields", temp.len()));
+    }
+    let (first, second) = temp[0].split_once('-')
+        .ok_or_else(|| format!("range \"{}\" is missing a '-'", temp[0]))?;
+    let first = first.parse::<u32>().map_err(|_| format!("\"{}\" is not a number", first))?;
+    let second = second.parse::<u32>().map_err(|_| format!("\"{}\" is not a number", second))?;
+    let mut chars = temp[1].chars();
+    let character = match (chars.next(), chars.next(), chars.next()) {
+        (Some(c), Some(':'), None) => c,
+        _ => return Err(format!("expected \"<char>:\", found \"{}\"", temp[1])),
+    };
     let password = temp[2];
 
     let p: Password = Password {
@@ -101,30 +134,5 @@ fn parse_password(password: String) -> (Password, PasswordPolicy) {
         second,
     };
 
-    (p, policy)
+    Ok((p, policy))
 }
-
-
-/// Check passwords match the sled_rental_policy
-/// 
-/// # Arguments
-/// 
-/// * password - the password to check
-/// * policy - the policy details to check against
-fn sled_rental_policy(p: &Password, policy: &PasswordPolicy) -> bool {
-    let c: u32 = p.password.matches(policy.character).count() as u32;
-    (c >= policy.first) && (c <= policy.second)
-}
-
-/// Check passwords match the sled_rental_policy
-/// 
-/// # Arguments
-/// 
-/// * password - the password to check
-/// * policy - the policy details to check against
-fn toboggon_corp_policy(p: &Password, policy: &PasswordPolicy) -> bool {
-    let first = p.password.chars().nth(policy.first as usize - 1).unwrap();
-    let second = p.password.chars().nth(policy.second as usize - 1).unwrap();
-
-    ((policy.character == first) || (policy.character == second)) && (first != second)
-}
\ No newline at end of file
@@ -0,0 +1,396 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+/// Representation of a password policy
+pub struct PasswordPolicy {
+    pub character: char,
+    pub first: u32,
+    pub second: u32,
+}
+
+/// Representation of a password
+pub struct Password {
+    pub password: String,
+}
+
+/// A rule a password can be checked against
+pub trait Policy {
+    /// Name used when reporting results for the policy
+    fn name(&self) -> &str;
+
+    /// Check a password against the policy
+    ///
+    /// # Arguments
+    ///
+    /// * `p` the password to check
+    /// * `policy` the policy details stored alongside the password
+    ///
+    /// # Returns
+    ///
+    /// * Ok if the password is valid, otherwise the reason it failed
+    fn check(&self, p: &Password, policy: &PasswordPolicy) -> Result<(), String>;
+}
+
+/// Sled rental policy - the character must appear between `first` and `second` times
+pub struct SledRentalPolicy;
+
+impl Policy for SledRentalPolicy {
+    fn name(&self) -> &str {
+        "sled rental"
+    }
+
+    fn check(&self, p: &Password, policy: &PasswordPolicy) -> Result<(), String> {
+        let c = p.password.matches(policy.character).count() as u32;
+        if c < policy.first || c > policy.second {
+            Err(format!("contains {} '{}' (needs {} to {})", c, policy.character, policy.first, policy.second))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Toboggan corporate policy - exactly one of positions `first` and `second` (1-indexed) must be the character
+pub struct TobogganCorpPolicy;
+
+impl Policy for TobogganCorpPolicy {
+    fn name(&self) -> &str {
+        "toboggan corporate"
+    }
+
+    fn check(&self, p: &Password, policy: &PasswordPolicy) -> Result<(), String> {
+        let first = nth_char(p, policy.first);
+        let second = nth_char(p, policy.second);
+        match (first == Some(policy.character), second == Some(policy.character)) {
+            (true, false) | (false, true) => Ok(()),
+            (true, true) => Err(format!("positions {} and {} are both '{}'", policy.first, policy.second, policy.character)),
+            (false, false) => Err(format!("neither position {} nor {} is '{}'", policy.first, policy.second, policy.character)),
+        }
+    }
+}
+
+/// A number in the policy language - either a literal or one of the numbers stored with the password
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Number {
+    Literal(u32),
+    First,
+    Second,
+}
+
+impl Number {
+    fn value(self, policy: &PasswordPolicy) -> u32 {
+        match self {
+            Number::Literal(n) => n,
+            Number::First => policy.first,
+            Number::Second => policy.second,
+        }
+    }
+}
+
+/// A character in the policy language - either a literal or the character stored with the password
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Character {
+    Literal(char),
+    Stored,
+}
+
+impl Character {
+    fn value(self, policy: &PasswordPolicy) -> char {
+        match self {
+            Character::Literal(c) => c,
+            Character::Stored => policy.character,
+        }
+    }
+}
+
+/// Expression in the policy language
+///
+/// ```text
+/// expr  := and ("or" and)*
+/// and   := unary ("and" unary)*
+/// unary := "not" unary | "(" expr ")" | atom
+/// atom  := "min" NUM CHAR        at least NUM occurrences of CHAR
+///        | "max" NUM CHAR        at most NUM occurrences of CHAR
+///        | "at" NUM CHAR         position NUM (1-indexed) is CHAR
+///        | "contains" STRING     the password contains STRING
+///        | "forbid" STRING       the password does not contain STRING
+/// NUM   := digits | $first | $second
+/// CHAR  := a single character | $char
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Min(Number, Character),
+    Max(Number, Character),
+    At(Number, Character),
+    Contains(String),
+    Forbid(String),
+    Not(Box<Expr>),
+    And(Vec<Expr>),
+    Or(Vec<Expr>),
+}
+
+impl Expr {
+    /// Parse an expression in the policy language
+    ///
+    /// # Arguments
+    ///
+    /// * `s` the expression string
+    pub fn parse(s: &str) -> Result<Expr, String> {
+        let tokens = tokenise(s)?;
+        let mut pos = 0;
+        let expr = parse_or(&tokens, &mut pos)?;
+        match tokens.get(pos) {
+            None => Ok(expr),
+            Some(t) => Err(format!("unexpected \"{}\" after end of expression", t)),
+        }
+    }
+
+    /// Evaluate the expression against a password
+    ///
+    /// # Returns
+    ///
+    /// * Ok if the expression holds, otherwise the reason it doesn't
+    pub fn evaluate(&self, p: &Password, policy: &PasswordPolicy) -> Result<(), String> {
+        let holds = |ok: bool| if ok { Ok(()) } else { Err(self.describe_failure(p, policy)) };
+        match self {
+            Expr::Min(n, c) => holds(p.password.matches(c.value(policy)).count() as u32 >= n.value(policy)),
+            Expr::Max(n, c) => holds(p.password.matches(c.value(policy)).count() as u32 <= n.value(policy)),
+            Expr::At(n, c) => holds(nth_char(p, n.value(policy)) == Some(c.value(policy))),
+            Expr::Contains(s) => holds(p.password.contains(s.as_str())),
+            Expr::Forbid(s) => holds(!p.password.contains(s.as_str())),
+            Expr::Not(e) => match e.evaluate(p, policy) {
+                Ok(()) => Err(format!("should not: {}", e.describe(policy))),
+                Err(_) => Ok(()),
+            },
+            Expr::And(es) => es.iter().try_for_each(|e| e.evaluate(p, policy)),
+            Expr::Or(es) => {
+                let mut reasons = Vec::new();
+                for e in es {
+                    match e.evaluate(p, policy) {
+                        Ok(()) => return Ok(()),
+                        Err(reason) => reasons.push(reason),
+                    }
+                }
+                Err(reasons.join(" and "))
+            }
+        }
+    }
+
+    /// Describe what the expression requires (with stored values filled in)
+    pub fn describe(&self, policy: &PasswordPolicy) -> String {
+        match self {
+            Expr::Min(n, c) => format!("at least {} '{}'", n.value(policy), c.value(policy)),
+            Expr::Max(n, c) => format!("at most {} '{}'", n.value(policy), c.value(policy)),
+            Expr::At(n, c) => format!("position {} is '{}'", n.value(policy), c.value(policy)),
+            Expr::Contains(s) => format!("contains \"{}\"", s),
+            Expr::Forbid(s) => format!("does not contain \"{}\"", s),
+            Expr::Not(e) => format!("not ({})", e.describe(policy)),
+            Expr::And(es) => es.iter().map(|e| e.describe(policy)).collect::<Vec<_>>().join(" and "),
+            Expr::Or(es) => es.iter().map(|e| format!("({})", e.describe(policy))).collect::<Vec<_>>().join(" or "),
+        }
+    }
+
+    /// Explain why an atom failed for the given password
+    fn describe_failure(&self, p: &Password, policy: &PasswordPolicy) -> String {
+        match self {
+            Expr::Min(_, c) | Expr::Max(_, c) => {
+                let c = c.value(policy);
+                format!("contains {} '{}' (needs {})", p.password.matches(c).count(), c, self.describe(policy))
+            }
+            Expr::At(n, c) => match nth_char(p, n.value(policy)) {
+                Some(found) => format!("position {} is '{}' not '{}'", n.value(policy), found, c.value(policy)),
+                None => format!("position {} is past the end of the password", n.value(policy)),
+            },
+            Expr::Contains(s) => format!("does not contain \"{}\"", s),
+            Expr::Forbid(s) => format!("contains forbidden \"{}\"", s),
+            e => e.describe(policy),
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fn num(n: &Number) -> String {
+            match n {
+                Number::Literal(n) => n.to_string(),
+                Number::First => "$first".to_string(),
+                Number::Second => "$second".to_string(),
+            }
+        }
+        fn chr(c: &Character) -> String {
+            match c {
+                Character::Literal(c) => c.to_string(),
+                Character::Stored => "$char".to_string(),
+            }
+        }
+        match self {
+            Expr::Min(n, c) => write!(f, "min {} {}", num(n), chr(c)),
+            Expr::Max(n, c) => write!(f, "max {} {}", num(n), chr(c)),
+            Expr::At(n, c) => write!(f, "at {} {}", num(n), chr(c)),
+            Expr::Contains(s) => write!(f, "contains \"{}\"", s),
+            Expr::Forbid(s) => write!(f, "forbid \"{}\"", s),
+            Expr::Not(e) => write!(f, "not ({})", e),
+            Expr::And(es) => write!(f, "{}", es.iter().map(|e| format!("({})", e)).collect::<Vec<_>>().join(" and ")),
+            Expr::Or(es) => write!(f, "{}", es.iter().map(|e| format!("({})", e)).collect::<Vec<_>>().join(" or ")),
+        }
+    }
+}
+
+/// Get the nth (1-indexed) character of a password
+fn nth_char(p: &Password, n: u32) -> Option<char> {
+    if n == 0 {
+        return None;
+    }
+    p.password.chars().nth(n as usize - 1)
+}
+
+/// Split an expression into tokens - words, parentheses and quoted strings (kept with their quotes)
+fn tokenise(s: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = s.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            c if c.is_whitespace() => (),
+            '(' | ')' => tokens.push(ch.to_string()),
+            '"' => {
+                let mut token = String::from("\"");
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some(c) => token.push(c),
+                        None => return Err(format!("unterminated string {}", token)),
+                    }
+                }
+                tokens.push(token);
+            }
+            _ => {
+                let mut token = ch.to_string();
+                while let Some(c) = chars.peek() {
+                    if c.is_whitespace() || *c == '(' || *c == ')' || *c == '"' {
+                        break;
+                    }
+                    token.push(*c);
+                    chars.next();
+                }
+                tokens.push(token);
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+fn parse_or(tokens: &[String], pos: &mut usize) -> Result<Expr, String> {
+    let mut terms = vec!(parse_and(tokens, pos)?);
+    while tokens.get(*pos).map(|t| t.as_str()) == Some("or") {
+        *pos += 1;
+        terms.push(parse_and(tokens, pos)?);
+    }
+    Ok(if terms.len() == 1 { terms.pop().unwrap() } else { Expr::Or(terms) })
+}
+
+fn parse_and(tokens: &[String], pos: &mut usize) -> Result<Expr, String> {
+    let mut terms = vec!(parse_unary(tokens, pos)?);
+    while tokens.get(*pos).map(|t| t.as_str()) == Some("and") {
+        *pos += 1;
+        terms.push(parse_unary(tokens, pos)?);
+    }
+    Ok(if terms.len() == 1 { terms.pop().unwrap() } else { Expr::And(terms) })
+}
+
+fn parse_unary(tokens: &[String], pos: &mut usize) -> Result<Expr, String> {
+    let token = tokens.get(*pos).ok_or("unexpected end of expression")?;
+    *pos += 1;
+    match token.as_str() {
+        "not" => Ok(Expr::Not(Box::new(parse_unary(tokens, pos)?))),
+        "(" => {
+            let expr = parse_or(tokens, pos)?;
+            match tokens.get(*pos).map(|t| t.as_str()) {
+                Some(")") => {
+                    *pos += 1;
+                    Ok(expr)
+                }
+                _ => Err("missing \")\"".to_string()),
+            }
+        }
+        "min" => Ok(Expr::Min(parse_number(tokens, pos)?, parse_character(tokens, pos)?)),
+        "max" => Ok(Expr::Max(parse_number(tokens, pos)?, parse_character(tokens, pos)?)),
+        "at" => Ok(Expr::At(parse_number(tokens, pos)?, parse_character(tokens, pos)?)),
+        "contains" => Ok(Expr::Contains(parse_string(tokens, pos)?)),
+        "forbid" => Ok(Expr::Forbid(parse_string(tokens, pos)?)),
+        t => Err(format!("unknown rule \"{}\"", t)),
+    }
+}
+
+fn parse_number(tokens: &[String], pos: &mut usize) -> Result<Number, String> {
+    let token = tokens.get(*pos).ok_or("expected a number")?;
+    *pos += 1;
+    match token.as_str() {
+        "$first" => Ok(Number::First),
+        "$second" => Ok(Number::Second),
+        t => t.parse().map(Number::Literal).map_err(|_| format!("expected a number, found \"{}\"", t)),
+    }
+}
+
+fn parse_character(tokens: &[String], pos: &mut usize) -> Result<Character, String> {
+    let token = tokens.get(*pos).ok_or("expected a character")?;
+    *pos += 1;
+    let mut chars = token.chars();
+    match (token.as_str(), chars.next(), chars.next()) {
+        ("$char", _, _) => Ok(Character::Stored),
+        (_, Some(c), None) => Ok(Character::Literal(c)),
+        (t, _, _) => Err(format!("expected a single character, found \"{}\"", t)),
+    }
+}
+
+fn parse_string(tokens: &[String], pos: &mut usize) -> Result<String, String> {
+    let token = tokens.get(*pos).ok_or("expected a quoted string")?;
+    *pos += 1;
+    match token.strip_prefix('"') {
+        Some(s) => Ok(s.to_string()),
+        None => Err(format!("expected a quoted string, found \"{}\"", token)),
+    }
+}
+
+/// A policy written in the policy language
+pub struct RulePolicy {
+    pub name: String,
+    pub expr: Expr,
+}
+
+impl Policy for RulePolicy {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn check(&self, p: &Password, policy: &PasswordPolicy) -> Result<(), String> {
+        self.expr.evaluate(p, policy)
+    }
+}
+
+/// Load policies from a config file. Each line is `name: expression`, blank lines and lines starting with # are ignored.
+///
+/// # Arguments
+///
+/// * `filename` the config file to read
+///
+/// # Returns
+///
+/// * The policies or an error naming the first line that couldn't be parsed
+pub fn load_policies(filename: &str) -> Result<Vec<RulePolicy>, String> {
+    let file = File::open(filename).map_err(|e| format!("{}: {}", filename, e))?;
+    let reader = BufReader::new(file);
+
+    let mut policies = Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| format!("{}: {}", filename, e))?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (name, expr) = line.split_once(':')
+            .ok_or_else(|| format!("{} line {}: expected \"name: expression\"", filename, i + 1))?;
+        let expr = Expr::parse(expr).map_err(|e| format!("{} line {}: {}", filename, i + 1, e))?;
+        policies.push(RulePolicy { name: name.trim().to_string(), expr });
+    }
+    Ok(policies)
+}
@@ -72,56 +72,35 @@ What do you get if you multiply together the number of trees encountered on each
 */
 
 
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::env;
+use std::fs;
 
-fn main() {
-    let filename = "src/map.txt";
+mod tree_map;
+use tree_map::{Slope, TreeMap};
 
-    let a = count_collisions(filename, 1, 1);
-    let b = count_collisions(filename, 3, 1);
-    let c = count_collisions(filename, 5, 1);
-    let d = count_collisions(filename, 7, 1);
-    let e = count_collisions(filename, 1, 2);
+fn main() {
+    let map = TreeMap::load("src/map.txt");
 
-    println!("Part 1 answer is {}", b);
-    println!("Part 2 answer is {}", a * b * c * d * e);
-}
+    let slopes = [Slope::new(1, 1), Slope::new(3, 1), Slope::new(5, 1), Slope::new(7, 1), Slope::new(1, 2)];
+    for slope in &slopes {
+        println!("Trees Hit for path {},{}: {}", slope.right, slope.down, map.count_collisions(*slope));
+    }
 
-/// Count collisions of the toboggon on a defined route
-/// 
-/// # Arguments
-/// 
-/// * `filename` - the file to get the map from
-/// * `right` - the distance to go right each step
-/// * `down` - the distance to go down each step
-/// 
-/// # Returns
-/// 
-/// * The number of collisions with trees
-fn count_collisions(filename: &str, right: usize, down: usize) -> u32 {
-    let tree: char = '#';
-    // Open the file in read-only mode (ignoring errors).
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-    // Read the file line by line using the lines() iterator from std::io::BufRead.
-    let mut counter = 0;
-    let mut x = 0;
-    let mut y = 0;
-    for (i, line) in reader.lines().enumerate() {
-        if i != y {
-            continue;
-        }
-        let temp: String = line.unwrap();
-        let length = temp.len();
-        let c = temp.chars().nth(x%length).unwrap();
-        if c == tree {
-            counter += 1;
+    println!("Part 1 answer is {}", map.count_collisions(Slope::new(3, 1)));
+    println!("Part 2 answer is {}", map.product_over_slopes(&slopes));
+
+    let (best, collisions) = map.best_slope(7, 2);
+    println!("Fewest trees hit with right -7..7, down 1..2: {} for path {},{}", collisions, best.right, best.down);
+
+    // Draw the route for a slope with `cargo run -- path <right> <down> [image.pbm]`
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.first().map(|a| a.as_str()) == Some("path") && args.len() >= 3 {
+        let slope = Slope::new(args[1].parse().unwrap(), args[2].parse().unwrap());
+        println!("\nRoute for path {},{} (O = tree hit, X = open square):", slope.right, slope.down);
+        print!("{}", map.path_to_text(slope));
+        if let Some(filename) = args.get(3) {
+            fs::write(filename, map.path_to_pbm(slope)).unwrap();
+            println!("Image written to {}", filename);
         }
-        x += right;
-        y += down;
     }
-
-    println!("Trees Hit for path {},{}: {}", right, down, counter);
-    counter
 }
@@ -0,0 +1,192 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+/// A slope to travel down the map - `right` may be negative to travel left
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Slope {
+    pub right: i64,
+    pub down: usize,
+}
+
+impl Slope {
+    pub fn new(right: i64, down: usize) -> Self {
+        Slope { right, down }
+    }
+}
+
+/// Map of the trees on the slope, stored as a bitset (1 = tree).
+/// The pattern repeats infinitely to the left and right.
+#[derive(Debug, Clone)]
+pub struct TreeMap {
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+    bits: Vec<u64>,
+}
+
+impl TreeMap {
+    /// Load a map from a file ('#' = tree, '.' = open square)
+    ///
+    /// # Arguments
+    ///
+    /// * `filename` the file to read
+    pub fn load(filename: &str) -> Self {
+        let file = File::open(filename).unwrap();
+        let reader = BufReader::new(file);
+        let lines: Vec<String> = reader.lines().map(|l| l.unwrap()).collect();
+        TreeMap::from_lines(&lines)
+    }
+
+    /// Create a map from rows of text ('#' = tree, anything else = open square)
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` the rows of the map from top to bottom - blank lines are ignored
+    pub fn from_lines<S: AsRef<str>>(lines: &[S]) -> Self {
+        let rows: Vec<&str> = lines.iter().map(|l| l.as_ref().trim_end()).filter(|l| !l.is_empty()).collect();
+        let width = rows.iter().map(|r| r.chars().count()).max().unwrap_or(0);
+        let words_per_row = width.div_ceil(64);
+        let mut bits = vec!(0u64; words_per_row * rows.len());
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                if c == '#' {
+                    bits[y * words_per_row + x / 64] |= 1 << (x % 64);
+                }
+            }
+        }
+        TreeMap { width, height: rows.len(), words_per_row, bits }
+    }
+
+    /// Check whether there is a tree at the given position (x wraps around in both directions)
+    ///
+    /// # Arguments
+    ///
+    /// * `x` the column - may be any value as the pattern repeats
+    /// * `y` the row (0 is the top)
+    pub fn is_tree(&self, x: i64, y: usize) -> bool {
+        if y >= self.height || self.width == 0 {
+            return false;
+        }
+        let x = x.rem_euclid(self.width as i64) as usize;
+        self.bits[y * self.words_per_row + x / 64] & (1 << (x % 64)) != 0
+    }
+
+    /// The positions visited travelling from the top left corner to the bottom of the map
+    ///
+    /// # Arguments
+    ///
+    /// * `slope` the slope to travel (down must be at least 1)
+    pub fn path(&self, slope: Slope) -> impl Iterator<Item = (i64, usize)> {
+        assert!(slope.down > 0, "Slope must travel down the map");
+        (0..self.height).step_by(slope.down).enumerate().map(move |(i, y)| (i as i64 * slope.right, y))
+    }
+
+    /// Count collisions of the toboggan with trees travelling the given slope
+    ///
+    /// # Arguments
+    ///
+    /// * `slope` the slope to travel (down must be at least 1)
+    ///
+    /// # Returns
+    ///
+    /// * The number of collisions with trees
+    pub fn count_collisions(&self, slope: Slope) -> usize {
+        self.path(slope).filter(|(x, y)| self.is_tree(*x, *y)).count()
+    }
+
+    /// Multiply together the number of collisions for each slope
+    ///
+    /// # Arguments
+    ///
+    /// * `slopes` the slopes to travel
+    pub fn product_over_slopes(&self, slopes: &[Slope]) -> u64 {
+        slopes.iter().map(|s| self.count_collisions(*s) as u64).product()
+    }
+
+    /// Find the slope with the fewest collisions (ties go to the first slope found)
+    ///
+    /// # Arguments
+    ///
+    /// * `max_right` largest distance to travel left or right each step
+    /// * `max_down` largest distance to travel down each step
+    ///
+    /// # Returns
+    ///
+    /// * The best slope and its number of collisions
+    pub fn best_slope(&self, max_right: i64, max_down: usize) -> (Slope, usize) {
+        let mut best = (Slope::new(0, 1), self.count_collisions(Slope::new(0, 1)));
+        for down in 1..=max_down {
+            for right in -max_right..=max_right {
+                let slope = Slope::new(right, down);
+                let collisions = self.count_collisions(slope);
+                if collisions < best.1 {
+                    best = (slope, collisions);
+                }
+            }
+        }
+        best
+    }
+
+    /// Draw the map with the route for a slope overlaid, tiling the pattern horizontally as far as the route goes.
+    /// Trees are '#' and open squares '.', squares on the route are 'O' if a tree was hit and 'X' otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `slope` the slope to travel (down must be at least 1)
+    ///
+    /// # Returns
+    ///
+    /// * The rows of the drawing from top to bottom
+    pub fn draw_path(&self, slope: Slope) -> Vec<Vec<char>> {
+        let width = self.width as i64;
+        let path: Vec<(i64, usize)> = self.path(slope).collect();
+        // Whole copies of the pattern needed to cover the route
+        let min_x = path.iter().map(|p| p.0).min().unwrap_or(0);
+        let max_x = path.iter().map(|p| p.0).max().unwrap_or(0);
+        let first_tile = min_x.div_euclid(width.max(1));
+        let last_tile = max_x.div_euclid(width.max(1));
+        let left = first_tile * width;
+        let right = (last_tile + 1) * width;
+
+        let mut rows: Vec<Vec<char>> = (0..self.height)
+            .map(|y| (left..right).map(|x| if self.is_tree(x, y) { '#' } else { '.' }).collect())
+            .collect();
+        for (x, y) in path {
+            let c = &mut rows[y][(x - left) as usize];
+            *c = if *c == '#' { 'O' } else { 'X' };
+        }
+        rows
+    }
+
+    /// Draw the route for a slope as text (see `draw_path`)
+    pub fn path_to_text(&self, slope: Slope) -> String {
+        self.draw_path(slope).iter().map(|row| row.iter().collect::<String>() + "\n").collect()
+    }
+
+    /// Draw the route for a slope as a plain (P1) PBM image with each square 3x3 pixels.
+    /// Trees are solid, open squares are blank, hit trees are a ring and open squares on the route are a cross.
+    pub fn path_to_pbm(&self, slope: Slope) -> String {
+        let drawing = self.draw_path(slope);
+        let width = drawing.first().map(|r| r.len()).unwrap_or(0);
+        let mut pbm = format!("P1\n# Toboggan route right {} down {}\n{} {}\n", slope.right, slope.down, width * 3, drawing.len() * 3);
+        for row in &drawing {
+            for py in 0..3 {
+                let pixels: String = row.iter().flat_map(|c| {
+                    let block: [char; 3] = match c {
+                        '#' => ['1', '1', '1'],
+                        'O' => if py == 1 { ['1', '0', '1'] } else { ['1', '1', '1'] },
+                        'X' => if py == 1 { ['0', '1', '0'] } else { ['1', '0', '1'] },
+                        _ => ['0', '0', '0'],
+                    };
+                    block
+                }).collect();
+                // Plain PBM lines should be no longer than 70 characters
+                for chunk in pixels.as_bytes().chunks(70) {
+                    pbm.push_str(std::str::from_utf8(chunk).unwrap());
+                    pbm.push('\n');
+                }
+            }
+        }
+        pbm
+    }
+}
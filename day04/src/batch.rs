@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+
+/// A passport read from a batch file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PassportRecord {
+    /// Position of the record in the batch (starting at 1)
+    pub record: usize,
+    /// Line the record starts on (starting at 1)
+    pub line: usize,
+    pub fields: HashMap<String, String>,
+}
+
+/// Something noticed while reading a record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    /// A key appeared more than once - the first value is kept
+    DuplicateKey { key: String, kept: String, ignored: String },
+    /// A key not in the list of known keys - the field is kept
+    UnknownKey(String),
+    /// A token with no colon separating the key from the value
+    MissingColon(String),
+    /// A token starting with a colon
+    EmptyKey(String),
+}
+
+/// A problem found on a line of a batch file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub record: usize,
+    pub line: usize,
+    pub kind: DiagnosticKind,
+}
+
+impl Diagnostic {
+    /// Whether the problem stops the record being used (rather than just being reported)
+    pub fn is_error(&self) -> bool {
+        matches!(self.kind, DiagnosticKind::MissingColon(_) | DiagnosticKind::EmptyKey(_))
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "record {} (line {}): ", self.record, self.line)?;
+        match &self.kind {
+            DiagnosticKind::DuplicateKey { key, kept, ignored } =>
+                write!(f, "duplicate key {} - kept \"{}\", ignored \"{}\"", key, kept, ignored),
+            DiagnosticKind::UnknownKey(key) => write!(f, "unknown key {}", key),
+            DiagnosticKind::MissingColon(token) => write!(f, "\"{}\" is not key:value", token),
+            DiagnosticKind::EmptyKey(token) => write!(f, "\"{}\" has no key", token),
+        }
+    }
+}
+
+/// The result of reading a batch file
+#[derive(Debug, Clone, Default)]
+pub struct Batch {
+    /// Records without errors (they may still have warnings in `diagnostics`)
+    pub records: Vec<PassportRecord>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl Batch {
+    /// Read a batch file
+    ///
+    /// # Arguments
+    ///
+    /// * `filename` the file to read
+    /// * `known_keys` the expected field names - any others are reported
+    pub fn read(filename: &str, known_keys: &[&str]) -> Result<Self, String> {
+        let text = fs::read_to_string(filename).map_err(|e| format!("{}: {}", filename, e))?;
+        Ok(Batch::parse(&text, known_keys))
+    }
+
+    /// Parse a batch of passports - records of whitespace separated key:value pairs divided by blank lines.
+    ///
+    /// Line endings may be LF or CRLF, any number of blank (or whitespace only) lines may separate records,
+    /// and values may contain colons (the key ends at the first colon).
+    ///
+    /// # Arguments
+    ///
+    /// * `text` the batch file contents
+    /// * `known_keys` the expected field names - any others are reported
+    pub fn parse(text: &str, known_keys: &[&str]) -> Self {
+        let mut batch = Batch::default();
+        let mut current: Option<(PassportRecord, bool)> = None;
+        let mut count = 0;
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                batch.finish(current.take());
+                continue;
+            }
+            let (record, failed) = current.get_or_insert_with(|| {
+                count += 1;
+                (PassportRecord { record: count, line: i + 1, fields: HashMap::new() }, false)
+            });
+            for token in line.split_whitespace() {
+                let mut report = |kind| {
+                    let diagnostic = Diagnostic { record: record.record, line: i + 1, kind };
+                    *failed |= diagnostic.is_error();
+                    batch.diagnostics.push(diagnostic);
+                };
+                let (key, value) = match token.split_once(':') {
+                    Some(("", _)) => {
+                        report(DiagnosticKind::EmptyKey(token.to_string()));
+                        continue;
+                    }
+                    Some(pair) => pair,
+                    None => {
+                        report(DiagnosticKind::MissingColon(token.to_string()));
+                        continue;
+                    }
+                };
+                if !known_keys.contains(&key) {
+                    report(DiagnosticKind::UnknownKey(key.to_string()));
+                }
+                match record.fields.get(key) {
+                    Some(kept) => report(DiagnosticKind::DuplicateKey {
+                        key: key.to_string(),
+                        kept: kept.clone(),
+                        ignored: value.to_string(),
+                    }),
+                    None => {
+                        record.fields.insert(key.to_string(), value.to_string());
+                    }
+                }
+            }
+        }
+        batch.finish(current);
+        batch
+    }
+
+    /// Keep a finished record unless it had errors
+    fn finish(&mut self, record: Option<(PassportRecord, bool)>) {
+        if let Some((record, false)) = record {
+            self.records.push(record);
+        }
+    }
+}
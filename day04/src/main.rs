@@ -119,316 +119,46 @@ Count the number of valid passports - those that have all required fields and va
 */
 
 
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::collections::HashMap;
+mod batch;
+mod passport;
+mod pattern;
 
-fn main() {
-    let passports = read_in_passports("src/passports.txt");
-    println!("Passports with required fields = {}", check_required_fields(&passports));
+use batch::Batch;
+use passport::Schema;
 
-    let mut valid_passports = 0;
-    for passport in passports {
-        if validate_passport(&passport) {
-            valid_passports += 1;
+fn main() {
+    // Optional argument:
+    //   report - list each passport as either its typed fields or its validation errors,
+    //            along with any problems found reading the batch file
+    let report = std::env::args().nth(1).as_deref() == Some("report");
+
+    let schema = Schema::load("src/schema.txt").unwrap();
+    let known_keys: Vec<&str> = schema.fields.iter().map(|f| f.name.as_str()).collect();
+    let batch = Batch::read("src/passports.txt", &known_keys).unwrap();
+    if report {
+        for diagnostic in &batch.diagnostics {
+            println!("{}", diagnostic);
         }
     }
-    println!("Valid passports = {}", valid_passports);
-}
+    let with_required = batch.records.iter().filter(|p| schema.check_required(&p.fields).is_empty()).count();
+    println!("Passports with required fields = {}", with_required);
 
-
-
-// struct Passport {
-//     byr: Option<u32>,
-//     iyr: Option<u32>,
-//     eyr: Option<u32>,
-//     hgt: Option<Height>,
-//     hcl: Option<String>,
-//     ecl: Option<String>,
-//     pid: Option<String>,
-//     cid: Option<String>,
-// }
-
-// impl Passport {
-//     fn update_field<T>(&self, field: &str, value: T) {
-//         match field {
-//             "byr" => self.byr = Option::from(value),
-//             "iyr"
-//         }     
-//     }
-
-//     fn check_required_fields(&self) -> bool {
-//         self.byr.is_some() || self.iyr.is_some() || self.eyr.is_some() ||
-//         self.hgt.is_some() || self.hcl.is_some() || self.ecl.is_some() ||
-//         self.pid.is_some()
-//     }
-
-//     fn validate() {
-        
-//     }
-// }
-
-// struct Height {
-//     h: u32,
-//     unit: String,
-// }
-
-/// Read in the passports from the defined file and return as a vector of
-/// HashMap
-/// 
-/// # Arguments
-/// 
-/// * `filename` - the filename to read the passports from
-fn read_in_passports(filename: &str) -> Vec< HashMap<String, String> > {
-    let mut passports: Vec< HashMap<String, String> > = Vec::new();
-
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-    let mut lines = reader.lines();
-
-    // iterate over lines until end of file
-    loop {
-        let mut line = lines.next();
-        if line.is_none() {
-            break;
-        }
-        let mut entry = HashMap::new();
-        loop {
-            let lin = String::from(line.unwrap().unwrap().trim());
-            // let l = lin.trim();
-            if lin.is_empty() {
-                break;
-            }
-            for item in lin.split_whitespace() {
-                let mut pair = item.split(':');
-                let key = String::from(pair.next().unwrap());
-                let value = String::from(pair.next().unwrap());
-                entry.insert(key, value);
+    let mut valid_passports = 0;
+    for passport in &batch.records {
+        match schema.validate(&passport.fields) {
+            Ok(typed) => {
+                valid_passports += 1;
+                if report {
+                    println!("{}: valid - {}", passport.record, typed);
+                }
             }
-            line = lines.next();
-            if line.is_none() {
-                break;
+            Err(errors) => {
+                if report {
+                    let errors: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+                    println!("{}: invalid - {}", passport.record, errors.join("; "));
+                }
             }
         }
-        passports.push(entry);
-    }
-    passports
-}
-
-fn check_required_fields(passports: &Vec< HashMap<String, String> >) -> u32 {
-    // Check that all passports contain the required fields
-    // byr - iyr - eyr - hgt - hcl - ecl -pid
-    let required_fields = vec!(
-        String::from("byr"),
-        String::from("iyr"),
-        String::from("eyr"),
-        String::from("hgt"),
-        String::from("hcl"),
-        String::from("ecl"),
-        String::from("pid"));
-
-    let mut valid_passports_total = 0;
-    for passport in passports {
-        let mut valid = true;
-        for field in &required_fields {
-            if passport.contains_key(field) == false {
-                valid = false;
-                break;
-            }
-        }
-        valid_passports_total += if valid { 1 } else { 0 }; 
-    }
-    valid_passports_total
-}
-
-fn validate_passport(passport: &HashMap<String, String>) -> bool {
-    let mut result = true;
-
-    // Birth year
-    if let Some(byr) = passport.get("byr") {
-        result = result && validate_byr(byr);
-    } else {
-        result = false;
-    }
-
-    // Issue Year
-    if let Some(iyr) = passport.get("iyr") {
-        result = result && validate_iyr(iyr);
-    } else {
-        result = false;
-    }
-
-    // Expiration Year
-    if let Some(eyr) = passport.get("eyr") {
-        result = result && validate_eyr(eyr);
-    } else {
-        result = false;
-    }
-
-    // Height
-    if let Some(hgt) = passport.get("hgt") {
-        result = result && validate_hgt(hgt);
-    } else {
-        result = false;
-    }
-
-    // Hair Colour
-    if let Some(hcl) = passport.get("hcl") {
-        result = result && validate_hcl(hcl);
-    } else {
-        result = false;
-    }
-
-    // Eye Colour
-    if let Some(ecl) = passport.get("ecl") {
-        result = result && validate_ecl(ecl);
-    } else {
-        result = false;
-    }
-
-    // Passport ID
-    if let Some(pid) = passport.get("pid") {
-        result = result && validate_pid(pid);
-    } else {
-        result = false;
-    }
-
-    // Country ID - Ignored
-
-    result
-}
-
-fn validate_byr(field: &String) -> bool {
-    // Requirement are:
-    //    - four digits
-    //    - at least 1920 and at most 2002.
-
-    // Convert to number
-    if let Ok(year) = field.parse::< u32 >() {
-        year >= 1920 && year <= 2002
-    }
-    else {
-        false
-    }
-}
-
-fn validate_iyr(field: &String) -> bool {
-    // Requirement are:
-    //    - four digits
-    //    - at least 2010 and at most 2020.
-
-    // Convert to number
-    if let Ok(year) = field.parse::< u32 >() {
-        year >= 2010 && year <= 2020
-    }
-    else {
-        false
-    }
-}
-
-fn validate_eyr(field: &String) -> bool {
-    // Requirement are:
-    //    - four digits
-    //    - at least 2010 and at most 2020.
-
-    // Convert to number
-    if let Ok(year) = field.parse::< u32 >() {
-        year >= 2020 && year <= 2030
-    }
-    else {
-        false
-    }
-}
-
-fn validate_hgt(field: &String) -> bool {
-    // Requirement are:
-    //    - a number followed by cm or in
-    //    - if cm -> 150-193 inclusive
-    //    - if in -> 59-76 inclusive
-
-    // Remove last two letters
-    let chars = field.chars().count();
-    if chars > 2 {
-        let measurement: String = field.chars().take(chars - 2).collect();
-        let measurement_type: String = field.chars().rev().take(2).collect();
-        // Convert to number
-        if let Ok(value) = measurement.parse::< u32 >() {
-            match measurement_type.as_str() {
-                // Strings are reversed!!!
-                "mc" => value >= 150 && value <= 193,
-                "ni" => value >= 59 && value <= 76,
-                _ => false
-            }
-        }
-        else {
-            false
-        }
-    } else {
-        false
-    }
-}
-
-fn validate_hcl(field: &String) -> bool {
-    // Requirement are:
-    //    - starts with #
-    //    - followed by 6 0-9 or a-f charachters
-
-    let valid_chars = vec!('0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'a', 'b', 'c', 'd', 'e', 'f');
-    let validate_chars = | hcl: &String | -> bool {
-        let mut result = true;
-        for c in hcl.chars() {
-            result = result && valid_chars.contains(&c);
-        }
-        result        
-    };
-
-    let chars = field.chars().count();
-    if chars == 7 {
-        if field.chars().next().unwrap() == '#' {
-            let hcl: String = field.chars().rev().take(6).collect();
-            validate_chars(&hcl)
-        } else {
-            false
-        }
-    } else {
-        false
-    }
-}
-
-fn validate_ecl(field: &String) -> bool {
-    // Requirement are:
-    //    - exactly one of: amb blu brn gry grn hzl oth
-
-    let valid_colors = vec!(
-        String::from("amb"),
-        String::from("blu"),
-        String::from("brn"),
-        String::from("gry"),
-        String::from("grn"),
-        String::from("hzl"),
-        String::from("oth") );
-    
-    valid_colors.contains(field)
-}
-
-fn validate_pid(field: &String) -> bool {
-    // Requirement are:
-    //    - nine-digit number, including leading zeroes
-
-    let valid_chars = vec!('0', '1', '2', '3', '4', '5', '6', '7', '8', '9');
-    let validate_chars = | hcl: &String | -> bool {
-        let mut result = true;
-        for c in hcl.chars() {
-            result = result && valid_chars.contains(&c);
-        }
-        result        
-    };
-
-    let chars = field.chars().count();
-    if chars == 9 {
-            validate_chars(field)
-    } else {
-        false
     }
+    println!("Valid passports = {}", valid_passports);
 }
-
@@ -0,0 +1,323 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::str::FromStr;
+
+use crate::pattern::Pattern;
+
+/// Unit of a height measurement
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeightUnit {
+    Cm,
+    In,
+}
+
+/// A height - a number followed by cm or in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Height {
+    pub value: u32,
+    pub unit: HeightUnit,
+}
+
+impl FromStr for Height {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let (value, unit) = split_number(s);
+        let value = value.parse().map_err(|_| format!("\"{}\" does not start with a number", s))?;
+        let unit = match unit {
+            "cm" => HeightUnit::Cm,
+            "in" => HeightUnit::In,
+            u => return Err(format!("\"{}\" has unknown unit \"{}\" (expected cm or in)", s, u)),
+        };
+        Ok(Height { value, unit })
+    }
+}
+
+impl fmt::Display for Height {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.unit {
+            HeightUnit::Cm => write!(f, "{}cm", self.value),
+            HeightUnit::In => write!(f, "{}in", self.value),
+        }
+    }
+}
+
+/// A colour written as # followed by six hex digits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HexColour {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl FromStr for HexColour {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let digits = s.strip_prefix('#').ok_or_else(|| format!("\"{}\" does not start with #", s))?;
+        if digits.len() != 6 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("\"{}\" is not # followed by six hex digits", s));
+        }
+        let channel = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).unwrap();
+        Ok(HexColour { r: channel(0), g: channel(2), b: channel(4) })
+    }
+}
+
+impl fmt::Display for HexColour {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+/// A passport with each field converted to its type
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Passport {
+    pub byr: Option<u32>,
+    pub iyr: Option<u32>,
+    pub eyr: Option<u32>,
+    pub hgt: Option<Height>,
+    pub hcl: Option<HexColour>,
+    pub ecl: Option<String>,
+    pub pid: Option<String>,
+    pub cid: Option<String>,
+}
+
+impl Passport {
+    /// Convert the raw key:value fields of a passport into a typed passport
+    ///
+    /// # Arguments
+    ///
+    /// * `fields` the passport fields (unknown fields are ignored)
+    ///
+    /// # Returns
+    ///
+    /// * The passport or an error for each field that couldn't be converted
+    pub fn from_fields(fields: &HashMap<String, String>) -> Result<Self, Vec<FieldError>> {
+        let mut errors = Vec::new();
+        let typed = |name: &str| -> Option<String> { fields.get(name).cloned() };
+        fn convert<T: FromStr>(name: &str, value: Option<String>, errors: &mut Vec<FieldError>) -> Option<T>
+        where
+            T::Err: fmt::Display,
+        {
+            match value?.parse::<T>() {
+                Ok(v) => Some(v),
+                Err(e) => {
+                    errors.push(FieldError::new(name, e.to_string()));
+                    None
+                }
+            }
+        }
+        let passport = Passport {
+            byr: convert("byr", typed("byr"), &mut errors),
+            iyr: convert("iyr", typed("iyr"), &mut errors),
+            eyr: convert("eyr", typed("eyr"), &mut errors),
+            hgt: convert("hgt", typed("hgt"), &mut errors),
+            hcl: convert("hcl", typed("hcl"), &mut errors),
+            ecl: typed("ecl"),
+            pid: typed("pid"),
+            cid: typed("cid"),
+        };
+        if errors.is_empty() {
+            Ok(passport)
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl fmt::Display for Passport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fn show<T: fmt::Display>(v: &Option<T>) -> String {
+            v.as_ref().map(|x| x.to_string()).unwrap_or_else(|| "-".to_string())
+        }
+        write!(f, "byr:{} iyr:{} eyr:{} hgt:{} hcl:{} ecl:{} pid:{} cid:{}",
+               show(&self.byr), show(&self.iyr), show(&self.eyr), show(&self.hgt),
+               show(&self.hcl), show(&self.ecl), show(&self.pid), show(&self.cid))
+    }
+}
+
+/// A problem with a single passport field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: &str, message: String) -> Self {
+        FieldError { field: field.to_string(), message }
+    }
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// Split a value into its leading number and the remaining suffix (e.g. "182cm" => ("182", "cm"))
+fn split_number(s: &str) -> (&str, &str) {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    s.split_at(end)
+}
+
+/// An inclusive range of numbers, optionally with a unit suffix (e.g. 150..193cm)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Range {
+    pub min: u64,
+    pub max: u64,
+    pub unit: String,
+}
+
+impl Range {
+    fn contains(&self, value: &str) -> bool {
+        let (number, unit) = split_number(value);
+        unit == self.unit && number.parse::<u64>().map(|n| self.min <= n && n <= self.max).unwrap_or(false)
+    }
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}..{}{}", self.min, self.max, self.unit)
+    }
+}
+
+/// The rules for one passport field
+#[derive(Debug, Clone)]
+pub struct FieldSchema {
+    pub name: String,
+    pub required: bool,
+    /// The value must match all of these patterns
+    pub patterns: Vec<Pattern>,
+    /// The value must be within one of these ranges (if any are given)
+    pub ranges: Vec<Range>,
+    /// The value must be one of these (if given)
+    pub one_of: Option<Vec<String>>,
+}
+
+impl FieldSchema {
+    /// Check a field value against the rules
+    ///
+    /// # Returns
+    ///
+    /// * An error message for each rule broken
+    fn check(&self, value: &str) -> Vec<String> {
+        let mut problems = Vec::new();
+        for pattern in &self.patterns {
+            if !pattern.is_match(value) {
+                problems.push(format!("\"{}\" does not match {}", value, pattern.as_str()));
+            }
+        }
+        if !self.ranges.is_empty() && !self.ranges.iter().any(|r| r.contains(value)) {
+            let ranges: Vec<String> = self.ranges.iter().map(|r| r.to_string()).collect();
+            problems.push(format!("\"{}\" is not within {}", value, ranges.join(" or ")));
+        }
+        if let Some(allowed) = &self.one_of {
+            if !allowed.iter().any(|a| a == value) {
+                problems.push(format!("\"{}\" is not one of {}", value, allowed.join(", ")));
+            }
+        }
+        problems
+    }
+}
+
+/// Declarative passport validation rules, loaded from a file with one field per line:
+///
+/// ```text
+/// # field  required|optional  rules...
+/// byr required pattern=\d{4} range=1920..2002
+/// hgt required range=150..193cm range=59..76in
+/// ecl required one-of=amb,blu,brn,gry,grn,hzl,oth
+/// cid optional
+/// ```
+#[derive(Debug, Clone)]
+pub struct Schema {
+    pub fields: Vec<FieldSchema>,
+}
+
+impl Schema {
+    /// Load a schema from a file
+    ///
+    /// # Arguments
+    ///
+    /// * `filename` the file to read
+    pub fn load(filename: &str) -> Result<Self, String> {
+        let text = fs::read_to_string(filename).map_err(|e| format!("{}: {}", filename, e))?;
+        Schema::parse(&text).map_err(|e| format!("{}: {}", filename, e))
+    }
+
+    /// Parse a schema from text
+    ///
+    /// # Arguments
+    ///
+    /// * `text` the schema (one field per line, # starts a comment)
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut fields = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |message: String| format!("line {}: {}", i + 1, message);
+            let mut words = line.split_whitespace();
+            let name = words.next().unwrap().to_string();
+            let required = match words.next() {
+                Some("required") => true,
+                Some("optional") => false,
+                other => return Err(error(format!("expected required or optional after {}, found {:?}", name, other))),
+            };
+            let mut field = FieldSchema { name, required, patterns: Vec::new(), ranges: Vec::new(), one_of: None };
+            for rule in words {
+                match rule.split_once('=') {
+                    Some(("pattern", p)) => field.patterns.push(Pattern::parse(p).map_err(error)?),
+                    Some(("range", r)) => {
+                        let (min, rest) = r.split_once("..").ok_or_else(|| error(format!("range \"{}\" needs min..max", r)))?;
+                        let (max, unit) = split_number(rest);
+                        let min = min.parse().map_err(|_| error(format!("bad range minimum in \"{}\"", r)))?;
+                        let max = max.parse().map_err(|_| error(format!("bad range maximum in \"{}\"", r)))?;
+                        field.ranges.push(Range { min, max, unit: unit.to_string() });
+                    }
+                    Some(("one-of", values)) => field.one_of = Some(values.split(',').map(|v| v.to_string()).collect()),
+                    _ => return Err(error(format!("unknown rule \"{}\"", rule))),
+                }
+            }
+            fields.push(field);
+        }
+        Ok(Schema { fields })
+    }
+
+    /// Check a passport contains every required field
+    ///
+    /// # Returns
+    ///
+    /// * An error for each missing field
+    pub fn check_required(&self, passport: &HashMap<String, String>) -> Vec<FieldError> {
+        self.fields.iter()
+            .filter(|f| f.required && !passport.contains_key(&f.name))
+            .map(|f| FieldError::new(&f.name, "missing".to_string()))
+            .collect()
+    }
+
+    /// Check a passport against every rule in the schema and convert it to a typed passport
+    ///
+    /// # Arguments
+    ///
+    /// * `passport` the raw passport fields
+    ///
+    /// # Returns
+    ///
+    /// * The typed passport or an error for each rule broken
+    pub fn validate(&self, passport: &HashMap<String, String>) -> Result<Passport, Vec<FieldError>> {
+        let mut errors = self.check_required(passport);
+        for field in &self.fields {
+            if let Some(value) = passport.get(&field.name) {
+                errors.extend(field.check(value).into_iter().map(|m| FieldError::new(&field.name, m)));
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Passport::from_fields(passport)
+    }
+}
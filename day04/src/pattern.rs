@@ -0,0 +1,143 @@
+/// A small regular-expression-like pattern which must match a whole string.
+///
+/// Supported syntax:
+/// * literal characters (use `\` to escape special characters)
+/// * `.` any character
+/// * `\d` any digit
+/// * `[...]` a character class, e.g. `[0-9a-f]`
+/// * quantifiers `?`, `*`, `+`, `{n}` and `{n,m}` after any of the above
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    source: String,
+    items: Vec<(Matcher, usize, usize)>,
+}
+
+/// Matches a single character
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Matcher {
+    Any,
+    Literal(char),
+    Class(Vec<(char, char)>),
+}
+
+impl Matcher {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Matcher::Any => true,
+            Matcher::Literal(l) => *l == c,
+            Matcher::Class(ranges) => ranges.iter().any(|(a, b)| *a <= c && c <= *b),
+        }
+    }
+}
+
+impl Pattern {
+    /// Parse a pattern
+    ///
+    /// # Arguments
+    ///
+    /// * `source` the pattern text
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut items = Vec::new();
+        let mut chars = source.chars().peekable();
+        while let Some(c) = chars.next() {
+            let matcher = match c {
+                '.' => Matcher::Any,
+                '\\' => match chars.next() {
+                    Some('d') => Matcher::Class(vec!(('0', '9'))),
+                    Some(e) => Matcher::Literal(e),
+                    None => return Err(format!("pattern \"{}\" ends with \\", source)),
+                },
+                '[' => {
+                    let mut ranges = Vec::new();
+                    loop {
+                        let start = match chars.next() {
+                            Some(']') => break,
+                            Some(s) => s,
+                            None => return Err(format!("pattern \"{}\" has an unclosed [", source)),
+                        };
+                        if chars.peek() == Some(&'-') {
+                            chars.next();
+                            match chars.next() {
+                                Some(']') => {
+                                    // A trailing - is a literal
+                                    ranges.push((start, start));
+                                    ranges.push(('-', '-'));
+                                    break;
+                                }
+                                Some(end) => ranges.push((start, end)),
+                                None => return Err(format!("pattern \"{}\" has an unclosed [", source)),
+                            }
+                        } else {
+                            ranges.push((start, start));
+                        }
+                    }
+                    Matcher::Class(ranges)
+                }
+                '?' | '*' | '+' | '{' => return Err(format!("pattern \"{}\" has nothing to repeat before '{}'", source, c)),
+                c => Matcher::Literal(c),
+            };
+            let (min, max) = match chars.peek() {
+                Some('?') => {
+                    chars.next();
+                    (0, 1)
+                }
+                Some('*') => {
+                    chars.next();
+                    (0, usize::MAX)
+                }
+                Some('+') => {
+                    chars.next();
+                    (1, usize::MAX)
+                }
+                Some('{') => {
+                    chars.next();
+                    let mut spec = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(s) => spec.push(s),
+                            None => return Err(format!("pattern \"{}\" has an unclosed {{", source)),
+                        }
+                    }
+                    let bad = || format!("pattern \"{}\" has a bad repeat {{{}}}", source, spec);
+                    match spec.split_once(',') {
+                        Some((a, b)) => (a.trim().parse().map_err(|_| bad())?, b.trim().parse().map_err(|_| bad())?),
+                        None => {
+                            let n = spec.trim().parse().map_err(|_| bad())?;
+                            (n, n)
+                        }
+                    }
+                }
+                _ => (1, 1),
+            };
+            items.push((matcher, min, max));
+        }
+        Ok(Pattern { source: source.to_string(), items })
+    }
+
+    /// Check whether the whole of a string matches the pattern
+    pub fn is_match(&self, s: &str) -> bool {
+        let chars: Vec<char> = s.chars().collect();
+        Pattern::match_from(&self.items, &chars)
+    }
+
+    /// Try to match the remaining items against the remaining characters (backtracking on repeats)
+    fn match_from(items: &[(Matcher, usize, usize)], chars: &[char]) -> bool {
+        match items.split_first() {
+            None => chars.is_empty(),
+            Some(((matcher, min, max), rest)) => {
+                // Greedily find how many characters this item could consume then back off
+                let available = chars.iter().take_while(|c| matcher.matches(**c)).count().min(*max);
+                if available < *min {
+                    return false;
+                }
+                (*min..=available).rev().any(|n| Pattern::match_from(rest, &chars[n..]))
+            }
+        }
+    }
+
+    /// The text the pattern was parsed from
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+}
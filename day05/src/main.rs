@@ -52,35 +52,68 @@ Your seat wasn't at the very front or back, though; the seats with IDs +1 and -1
 What is the ID of your seat?
 */
 
+mod seat_codec;
+mod seat_map;
+
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 
+use seat_codec::SeatLayout;
+use seat_map::SeatMap;
+
 fn main() {
+    // Optional arguments:
+    //   --layout <row bits> <column bits> <row letters> <column letters>  e.g. --layout 7 3 FB LR
+    //   encode <seat id>   print the boarding pass for a seat
+    //   decode <pass>      print the seat for a boarding pass
+    //   map                draw the cabin showing occupied and free seats
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let mut layout = SeatLayout::standard();
+    if args.first().map(|a| a.as_str()) == Some("--layout") {
+        let letters = |s: &str| -> [char; 2] {
+            let chars: Vec<char> = s.chars().collect();
+            assert!(chars.len() == 2, "axis letters must be two characters, e.g. FB");
+            [chars[0], chars[1]]
+        };
+        layout = SeatLayout::new(args[1].parse().unwrap(), args[2].parse().unwrap(),
+                                 letters(&args[3]), letters(&args[4])).unwrap();
+        args.drain(..5);
+    }
+    match args.first().map(|a| a.as_str()) {
+        Some("encode") => {
+            let seat = layout.seat_from_id(args[1].parse().unwrap()).unwrap();
+            println!("Seat row {} column {} has boarding pass {}", seat.row, seat.column, layout.encode(seat).unwrap());
+            return;
+        }
+        Some("decode") => {
+            let seat = layout.decode(&args[1]).unwrap();
+            println!("Boarding pass {} is row {} column {} (id {})", args[1], seat.row, seat.column, layout.seat_id(seat));
+            return;
+        }
+        _ => {}
+    }
+
     let tickets = read_in_tickets("src/tickets.txt");
 
-    let mut max_id = 0;
-    let mut ticket_ids = Vec::new();
+    let mut seat_map = SeatMap::new(layout);
     for ticket in tickets {
-        let (row, column) = convert_ticket(&ticket);
-        let id = row * 8 + column;
-        if id > max_id {
-            max_id = id;
+        if let Err(e) = seat_map.add(&ticket) {
+            println!("Skipping boarding pass {}: {}", ticket, e);
         }
-        ticket_ids.push(id);
+    }
+    for d in seat_map.duplicates() {
+        println!("Duplicate boarding pass {} for row {} column {} (first pass {})",
+                 d.duplicate, d.seat.row, d.seat.column, d.first);
     }
 
-    ticket_ids.sort();
-
-    println!("Maximum id is {}", max_id);
-    // println!("Taken tickets are {:?}", ticket_ids);
+    if args.first().map(|a| a.as_str()) == Some("map") {
+        print!("{}", seat_map.render());
+        println!("{} seats occupied, {} empty", seat_map.occupied_count(), seat_map.empty_seats().len());
+    }
 
-    // Check through ordered tickets untill we find the missing one
-    let mut offset = ticket_ids[0];
-    for (i, id) in ticket_ids.iter().enumerate() {
-        if *id != i as u32 + offset {
-            println!("Seat id {} is free", i as u32 + offset);
-            offset += 1;
-        }        
+    println!("Maximum id is {}", seat_map.max_id().unwrap());
+    for id in seat_map.free_seats() {
+        println!("Seat id {} is free", id);
     }
 }
 
@@ -94,18 +127,5 @@ fn read_in_tickets(filename: &str) -> Vec<String> {
     let file = File::open(filename).unwrap();
     let reader = BufReader::new(file);
 
-    reader.lines().flatten().collect()
-}
-
-/// Returns a tuple of (row, column) represented by the ticket id string
-/// 
-/// # Arguments
-/// 
-/// * `ticket_id` - A string of the form BBFFBBFRLL
-fn convert_ticket(ticket_id: &str) -> (u32, u32) {
-    // Convert to binary - F,L -> 0, B,R -> 1
-    let temp = ticket_id.replace(&['B', 'R'][..], "1").replace(&['F', 'L'][..], "0");
-    let number = u32::from_str_radix(&temp, 2).unwrap();
-
-    (number >> 3, number & 7)
+    reader.lines().map(|l| l.unwrap().trim().to_string()).collect()
 }
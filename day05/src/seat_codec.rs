@@ -0,0 +1,151 @@
+use std::fmt;
+
+/// A seat on a plane
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Seat {
+    pub row: u32,
+    pub column: u32,
+}
+
+/// Errors encoding or decoding boarding passes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodecError {
+    /// The lower and upper letter for an axis are the same
+    AmbiguousLetters(char),
+    /// The layout has too many seats for the seat id to fit in a u32
+    TooManyBits(u32),
+    /// The boarding pass is the wrong length for the layout
+    WrongLength { expected: usize, found: usize },
+    /// A character which isn't one of the axis letters (position starts at 0)
+    IllegalCharacter { position: usize, character: char },
+    RowOutOfRange(u32),
+    ColumnOutOfRange(u32),
+    IdOutOfRange(u32),
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CodecError::AmbiguousLetters(c) => write!(f, "the letter '{}' is used for both halves of an axis", c),
+            CodecError::TooManyBits(bits) => write!(f, "{} bits is too many for a seat id", bits),
+            CodecError::WrongLength { expected, found } =>
+                write!(f, "boarding pass has {} characters, expected {}", found, expected),
+            CodecError::IllegalCharacter { position, character } =>
+                write!(f, "illegal character '{}' at position {}", character, position),
+            CodecError::RowOutOfRange(row) => write!(f, "row {} is not on the plane", row),
+            CodecError::ColumnOutOfRange(column) => write!(f, "column {} is not on the plane", column),
+            CodecError::IdOutOfRange(id) => write!(f, "seat id {} is not on the plane", id),
+        }
+    }
+}
+
+/// The binary space partitioning layout of a plane's boarding passes.
+///
+/// A pass is `row_bits` letters choosing the row followed by `column_bits` letters choosing the column.
+/// For each axis the first letter picks the lower half of the remaining seats and the second the upper half.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeatLayout {
+    row_bits: u32,
+    column_bits: u32,
+    row_letters: [char; 2],
+    column_letters: [char; 2],
+}
+
+impl SeatLayout {
+    /// Create a new layout
+    ///
+    /// # Arguments
+    ///
+    /// * `row_bits` number of letters choosing the row (the plane has 2^row_bits rows)
+    /// * `column_bits` number of letters choosing the column (the plane has 2^column_bits columns)
+    /// * `row_letters` the letters for the lower and upper half of the rows
+    /// * `column_letters` the letters for the lower and upper half of the columns
+    pub fn new(row_bits: u32, column_bits: u32, row_letters: [char; 2], column_letters: [char; 2]) -> Result<Self, CodecError> {
+        if row_bits + column_bits > 31 {
+            return Err(CodecError::TooManyBits(row_bits + column_bits));
+        }
+        for letters in [row_letters, column_letters].iter() {
+            if letters[0] == letters[1] {
+                return Err(CodecError::AmbiguousLetters(letters[0]));
+            }
+        }
+        Ok(SeatLayout { row_bits, column_bits, row_letters, column_letters })
+    }
+
+    /// The standard layout - 128 rows chosen with F/B and 8 columns chosen with L/R
+    pub fn standard() -> Self {
+        SeatLayout::new(7, 3, ['F', 'B'], ['L', 'R']).unwrap()
+    }
+
+    /// Number of rows on the plane
+    pub fn rows(&self) -> u32 {
+        1 << self.row_bits
+    }
+
+    /// Number of columns on the plane
+    pub fn columns(&self) -> u32 {
+        1 << self.column_bits
+    }
+
+    /// Number of characters in a boarding pass
+    pub fn pass_length(&self) -> usize {
+        (self.row_bits + self.column_bits) as usize
+    }
+
+    /// Decode a boarding pass into a seat
+    ///
+    /// # Arguments
+    ///
+    /// * `pass` the boarding pass, e.g. FBFBBFFRLR
+    pub fn decode(&self, pass: &str) -> Result<Seat, CodecError> {
+        let length = pass.chars().count();
+        if length != self.pass_length() {
+            return Err(CodecError::WrongLength { expected: self.pass_length(), found: length });
+        }
+        let mut seat = Seat { row: 0, column: 0 };
+        for (position, character) in pass.chars().enumerate() {
+            let (value, letters) = if position < self.row_bits as usize {
+                (&mut seat.row, self.row_letters)
+            } else {
+                (&mut seat.column, self.column_letters)
+            };
+            let bit = letters.iter().position(|l| *l == character)
+                .ok_or(CodecError::IllegalCharacter { position, character })?;
+            *value = (*value << 1) | bit as u32;
+        }
+        Ok(seat)
+    }
+
+    /// Encode a seat as a boarding pass
+    ///
+    /// # Arguments
+    ///
+    /// * `seat` the seat to encode
+    pub fn encode(&self, seat: Seat) -> Result<String, CodecError> {
+        if seat.row >= self.rows() {
+            return Err(CodecError::RowOutOfRange(seat.row));
+        }
+        if seat.column >= self.columns() {
+            return Err(CodecError::ColumnOutOfRange(seat.column));
+        }
+        let axis = |value: u32, bits: u32, letters: [char; 2]| {
+            (0..bits).rev().map(move |b| letters[((value >> b) & 1) as usize])
+        };
+        Ok(axis(seat.row, self.row_bits, self.row_letters)
+            .chain(axis(seat.column, self.column_bits, self.column_letters))
+            .collect())
+    }
+
+    /// The id of a seat - its row multiplied by the number of columns plus its column
+    pub fn seat_id(&self, seat: Seat) -> u32 {
+        seat.row * self.columns() + seat.column
+    }
+
+    /// The seat with the given id
+    pub fn seat_from_id(&self, id: u32) -> Result<Seat, CodecError> {
+        if id >= self.rows() * self.columns() {
+            return Err(CodecError::IdOutOfRange(id));
+        }
+        Ok(Seat { row: id / self.columns(), column: id % self.columns() })
+    }
+}
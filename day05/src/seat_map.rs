@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::seat_codec::{CodecError, Seat, SeatLayout};
+
+/// A boarding pass for a seat which already had one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicatePass {
+    pub seat: Seat,
+    pub first: String,
+    pub duplicate: String,
+}
+
+/// Occupancy of every seat on a plane, built from the boarding passes
+#[derive(Debug, Clone)]
+pub struct SeatMap {
+    layout: SeatLayout,
+    /// The boarding pass for each occupied seat id
+    passes: HashMap<u32, String>,
+    duplicates: Vec<DuplicatePass>,
+}
+
+impl SeatMap {
+    /// Create an empty seat map
+    ///
+    /// # Arguments
+    ///
+    /// * `layout` the layout of the plane
+    pub fn new(layout: SeatLayout) -> Self {
+        SeatMap { layout, passes: HashMap::new(), duplicates: Vec::new() }
+    }
+
+    /// Mark the seat for a boarding pass as occupied
+    ///
+    /// # Arguments
+    ///
+    /// * `pass` the boarding pass
+    ///
+    /// # Returns
+    ///
+    /// * The seat, or the reason the boarding pass couldn't be decoded.
+    ///   A second pass for the same seat is recorded as a duplicate.
+    pub fn add(&mut self, pass: &str) -> Result<Seat, CodecError> {
+        let seat = self.layout.decode(pass)?;
+        let id = self.layout.seat_id(seat);
+        match self.passes.get(&id) {
+            Some(first) => self.duplicates.push(DuplicatePass {
+                seat,
+                first: first.clone(),
+                duplicate: pass.to_string(),
+            }),
+            None => {
+                self.passes.insert(id, pass.to_string());
+            }
+        }
+        Ok(seat)
+    }
+
+    /// Whether the seat with the given id is occupied
+    pub fn is_occupied(&self, id: u32) -> bool {
+        self.passes.contains_key(&id)
+    }
+
+    /// Number of occupied seats
+    pub fn occupied_count(&self) -> usize {
+        self.passes.len()
+    }
+
+    /// The highest occupied seat id
+    pub fn max_id(&self) -> Option<u32> {
+        self.passes.keys().max().copied()
+    }
+
+    /// Every empty seat, in seat id order
+    pub fn empty_seats(&self) -> Vec<Seat> {
+        (0..self.layout.rows() * self.layout.columns())
+            .filter(|id| !self.is_occupied(*id))
+            .map(|id| self.layout.seat_from_id(id).unwrap())
+            .collect()
+    }
+
+    /// Ids of the empty seats where the seats with the ids either side are both occupied
+    pub fn free_seats(&self) -> Vec<u32> {
+        self.empty_seats().into_iter()
+            .map(|seat| self.layout.seat_id(seat))
+            .filter(|id| *id > 0 && self.is_occupied(id - 1) && self.is_occupied(id + 1))
+            .collect()
+    }
+
+    /// Boarding passes for seats which already had a pass, in the order they were added
+    pub fn duplicates(&self) -> &[DuplicatePass] {
+        &self.duplicates
+    }
+
+    /// Draw the cabin with a line per row - # for occupied seats, O for free seats
+    /// (empty with both neighbours occupied) and . for other empty seats
+    pub fn render(&self) -> String {
+        let free = self.free_seats();
+        let width = (self.layout.rows() - 1).to_string().len();
+        let mut out = String::new();
+        for row in 0..self.layout.rows() {
+            write!(out, "{:>width$} ", row, width = width).unwrap();
+            for column in 0..self.layout.columns() {
+                let id = self.layout.seat_id(Seat { row, column });
+                out.push(if self.is_occupied(id) {
+                    '#'
+                } else if free.contains(&id) {
+                    'O'
+                } else {
+                    '.'
+                });
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
@@ -0,0 +1,289 @@
+use std::fmt;
+
+/// The questions (a-z) answered "yes" by one person or a group, stored as a 26-bit set
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct AnswerSet(u32);
+
+impl AnswerSet {
+    /// Every question
+    pub const ALL: AnswerSet = AnswerSet((1 << 26) - 1);
+
+    /// Parse the questions answered by one person
+    ///
+    /// # Arguments
+    ///
+    /// * `line` the questions answered, e.g. "abx"
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let mut set = AnswerSet::default();
+        for c in line.trim().chars() {
+            if !c.is_ascii_lowercase() {
+                return Err(format!("\"{}\" contains '{}' which is not a question (a-z)", line, c));
+            }
+            set.0 |= AnswerSet::bit(c);
+        }
+        Ok(set)
+    }
+
+    fn bit(question: char) -> u32 {
+        1 << (question as u32 - 'a' as u32)
+    }
+
+    pub fn contains(&self, question: char) -> bool {
+        question.is_ascii_lowercase() && self.0 & AnswerSet::bit(question) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn union(self, other: AnswerSet) -> AnswerSet {
+        AnswerSet(self.0 | other.0)
+    }
+
+    pub fn intersection(self, other: AnswerSet) -> AnswerSet {
+        AnswerSet(self.0 & other.0)
+    }
+
+    pub fn symmetric_difference(self, other: AnswerSet) -> AnswerSet {
+        AnswerSet(self.0 ^ other.0)
+    }
+
+    pub fn difference(self, other: AnswerSet) -> AnswerSet {
+        AnswerSet(self.0 & !other.0)
+    }
+
+    /// The questions in the set in alphabetical order
+    pub fn questions(self) -> impl Iterator<Item = char> {
+        ('a'..='z').filter(move |c| self.contains(*c))
+    }
+}
+
+impl fmt::Display for AnswerSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.questions().collect::<String>())
+    }
+}
+
+/// The answers of each member of a group
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Group {
+    pub members: Vec<AnswerSet>,
+}
+
+impl Group {
+    /// Questions answered by anyone in the group
+    pub fn union(&self) -> AnswerSet {
+        self.members.iter().fold(AnswerSet::default(), |a, b| a.union(*b))
+    }
+
+    /// Questions answered by everyone in the group
+    pub fn intersection(&self) -> AnswerSet {
+        if self.members.is_empty() {
+            return AnswerSet::default();
+        }
+        self.members.iter().fold(AnswerSet::ALL, |a, b| a.intersection(*b))
+    }
+
+    /// Symmetric difference of every member's answers - the questions answered by an odd number of members
+    pub fn symmetric_difference(&self) -> AnswerSet {
+        self.members.iter().fold(AnswerSet::default(), |a, b| a.symmetric_difference(*b))
+    }
+
+    /// Number of members answering each question (index 0 is a)
+    pub fn tally(&self) -> [usize; 26] {
+        let mut counts = [0; 26];
+        for member in &self.members {
+            for q in member.questions() {
+                counts[(q as u8 - b'a') as usize] += 1;
+            }
+        }
+        counts
+    }
+
+    /// Questions answered by at least `k` members
+    pub fn at_least(&self, k: usize) -> AnswerSet {
+        self.select(|n| n >= k)
+    }
+
+    /// Questions answered by exactly `k` members
+    pub fn exactly(&self, k: usize) -> AnswerSet {
+        self.select(|n| n == k)
+    }
+
+    fn select<F: Fn(usize) -> bool>(&self, keep: F) -> AnswerSet {
+        let mut set = AnswerSet::default();
+        for (i, n) in self.tally().iter().enumerate() {
+            if *n > 0 && keep(*n) {
+                set.0 |= 1 << i;
+            }
+        }
+        set
+    }
+}
+
+/// Split the form responses into groups - one line per member, groups separated by blank lines
+///
+/// # Arguments
+///
+/// * `lines` the lines of the responses file
+pub fn parse_groups<I, S>(lines: I) -> Result<Vec<Group>, String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut groups = Vec::new();
+    let mut group = Group::default();
+    for (i, line) in lines.into_iter().enumerate() {
+        let line = line.as_ref();
+        if line.trim().is_empty() {
+            if !group.members.is_empty() {
+                groups.push(std::mem::take(&mut group));
+            }
+        } else {
+            group.members.push(AnswerSet::parse(line).map_err(|e| format!("line {}: {}", i + 1, e))?);
+        }
+    }
+    if !group.members.is_empty() {
+        groups.push(group);
+    }
+    Ok(groups)
+}
+
+/// A query selecting a set of questions from a group.
+///
+/// Queries are built from these terms:
+/// * `any` questions answered by anyone (union)
+/// * `all` questions answered by everyone (intersection)
+/// * `odd` questions answered by an odd number of members (symmetric difference)
+/// * `at-least <k>` questions answered by at least k members
+/// * `exactly <k>` questions answered by exactly k members
+/// * `exactly-one` questions answered by exactly one member
+///
+/// combined with `|` (union), `&` (intersection), `^` (symmetric difference) and `-` (difference),
+/// evaluated left to right, with parentheses for grouping - e.g. `at-least 2 - all`.
+/// `-` must have spaces either side as it also appears in term names.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Query {
+    Any,
+    All,
+    Odd,
+    AtLeast(usize),
+    Exactly(usize),
+    Union(Box<Query>, Box<Query>),
+    Intersection(Box<Query>, Box<Query>),
+    SymmetricDifference(Box<Query>, Box<Query>),
+    Difference(Box<Query>, Box<Query>),
+}
+
+impl Query {
+    /// Parse a query
+    ///
+    /// # Arguments
+    ///
+    /// * `text` the query, e.g. "any - exactly-one"
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let spaced = text.replace('(', " ( ").replace(')', " ) ")
+            .replace('|', " | ").replace('&', " & ").replace('^', " ^ ");
+        let tokens: Vec<&str> = spaced.split_whitespace().collect();
+        let mut position = 0;
+        let query = Query::parse_expression(&tokens, &mut position)?;
+        match tokens.get(position) {
+            None => Ok(query),
+            Some(t) => Err(format!("unexpected \"{}\" in query \"{}\"", t, text)),
+        }
+    }
+
+    fn parse_expression(tokens: &[&str], position: &mut usize) -> Result<Self, String> {
+        let mut query = Query::parse_term(tokens, position)?;
+        while let Some(op) = tokens.get(*position) {
+            let combine: fn(Box<Query>, Box<Query>) -> Query = match *op {
+                "|" => Query::Union,
+                "&" => Query::Intersection,
+                "^" => Query::SymmetricDifference,
+                "-" => Query::Difference,
+                _ => break,
+            };
+            *position += 1;
+            let right = Query::parse_term(tokens, position)?;
+            query = combine(Box::new(query), Box::new(right));
+        }
+        Ok(query)
+    }
+
+    fn parse_term(tokens: &[&str], position: &mut usize) -> Result<Self, String> {
+        let token = tokens.get(*position).ok_or("query ended unexpectedly")?;
+        *position += 1;
+        let mut count = || -> Result<usize, String> {
+            let n = tokens.get(*position).ok_or(format!("{} needs a number", token))?;
+            *position += 1;
+            n.parse().map_err(|_| format!("{} needs a number, found \"{}\"", token, n))
+        };
+        match *token {
+            "any" => Ok(Query::Any),
+            "all" => Ok(Query::All),
+            "odd" => Ok(Query::Odd),
+            "exactly-one" => Ok(Query::Exactly(1)),
+            "at-least" => Ok(Query::AtLeast(count()?)),
+            "exactly" => Ok(Query::Exactly(count()?)),
+            "(" => {
+                let query = Query::parse_expression(tokens, position)?;
+                match tokens.get(*position) {
+                    Some(&")") => {
+                        *position += 1;
+                        Ok(query)
+                    }
+                    _ => Err("missing )".to_string()),
+                }
+            }
+            t => Err(format!("unknown query term \"{}\"", t)),
+        }
+    }
+
+    /// Evaluate the query for a group
+    pub fn evaluate(&self, group: &Group) -> AnswerSet {
+        match self {
+            Query::Any => group.union(),
+            Query::All => group.intersection(),
+            Query::Odd => group.symmetric_difference(),
+            Query::AtLeast(k) => group.at_least(*k),
+            Query::Exactly(k) => group.exactly(*k),
+            Query::Union(a, b) => a.evaluate(group).union(b.evaluate(group)),
+            Query::Intersection(a, b) => a.evaluate(group).intersection(b.evaluate(group)),
+            Query::SymmetricDifference(a, b) => a.evaluate(group).symmetric_difference(b.evaluate(group)),
+            Query::Difference(a, b) => a.evaluate(group).difference(b.evaluate(group)),
+        }
+    }
+
+    /// Sum of the number of questions selected by the query over every group
+    pub fn total(&self, groups: &[Group]) -> usize {
+        groups.iter().map(|g| self.evaluate(g).len()).sum()
+    }
+}
+
+/// How one question was answered across every group
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QuestionStatistics {
+    pub question: char,
+    /// Number of people answering yes
+    pub people: usize,
+    /// Number of groups where anyone answered yes
+    pub groups_any: usize,
+    /// Number of groups where everyone answered yes
+    pub groups_all: usize,
+}
+
+/// Statistics for each question (a-z) across every group
+pub fn question_statistics(groups: &[Group]) -> Vec<QuestionStatistics> {
+    let mut stats: Vec<QuestionStatistics> = ('a'..='z')
+        .map(|question| QuestionStatistics { question, ..Default::default() })
+        .collect();
+    for group in groups {
+        let (any, all) = (group.union(), group.intersection());
+        for (s, n) in stats.iter_mut().zip(group.tally().iter()) {
+            s.people += n;
+            s.groups_any += any.contains(s.question) as usize;
+            s.groups_all += all.contains(s.question) as usize;
+        }
+    }
+    stats
+}
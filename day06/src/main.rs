@@ -1,4 +1,4 @@
-/**
+/*
  * --- Day 6: Custom Customs ---
  * 
  * As your flight approaches the regional airport where you'll switch to a much larger plane, customs declaration forms are distributed to the passengers.
@@ -74,26 +74,38 @@
  * For each group, count the number of questions to which everyone answered "yes". What is the sum of those counts?
  */
 
+mod answers;
+
 use std::fs::File;
 use std::io::{BufRead, BufReader};
-use std::collections::HashMap;
+
+use answers::{parse_groups, question_statistics, Query};
 
 fn main() {
-    let lines = read_in_file("src/formresponses.txt");
-    let groups = seperate_groups(lines);
+    // Optional arguments:
+    //   query <query>  total the questions selected by a query over every group, e.g. query "at-least 2 - all"
+    //   stats          show how each question was answered across all groups
+    let args: Vec<String> = std::env::args().skip(1).collect();
 
-    let mut count = 0;
-    for group in groups.iter() {
-        count += count_unique_responses(group);
-    }
-    println!("Total unique responses: {}", count);
+    let lines = read_in_file("src/formresponses.txt");
+    let groups = parse_groups(&lines).unwrap();
 
-    let mut count = 0;
-    for group in groups.iter() {
-        count += count_non_unique_responses(group);
+    match args.first().map(|a| a.as_str()) {
+        Some("query") => {
+            let query = Query::parse(&args[1]).unwrap();
+            println!("Total for {}: {}", args[1], query.total(&groups));
+        }
+        Some("stats") => {
+            println!("question  people  groups (any)  groups (all)");
+            for s in question_statistics(&groups) {
+                println!("{:>8}  {:>6}  {:>12}  {:>12}", s.question, s.people, s.groups_any, s.groups_all);
+            }
+        }
+        _ => {
+            println!("Total unique responses: {}", Query::Any.total(&groups));
+            println!("Total non-unique responses: {}", Query::All.total(&groups));
+        }
     }
-    println!("Total non-unique responses: {}", count);
-
 }
 
 /// Read in lines of a file to a vector
@@ -106,48 +118,5 @@ fn read_in_file(filename: &str) -> Vec<String> {
     let file = File::open(filename).unwrap();
     let reader = BufReader::new(file);
 
-    reader.lines().flatten().collect()
-}
-
-fn seperate_groups(input: Vec<String>) -> Vec<Vec<String>> {
-    let mut output = Vec::new();
-    let mut group = Vec::new();
-    for line in input {
-        if line.trim().is_empty() {
-            output.push(group.clone());
-            group = Vec::new();
-        } else {
-            group.push(line);
-        }
-    }
-    output.push(group.clone());
-    output
-}
-
-fn count_unique_responses(input: &Vec<String>) -> usize {
-    let mut unique_responses = HashMap::new();
-    for line in input {
-        for c in line.chars() {
-            *unique_responses.entry(c).or_insert(1) += 1;
-        }
-    }
-    unique_responses.len()
-}
-
-fn count_non_unique_responses(input: &Vec<String>) -> usize {
-    let mut responses = HashMap::new();
-    let group_size = input.len();
-    for line in input {
-        for c in line.chars() {
-            *responses.entry(c).or_insert(0) += 1;
-        }
-    }
-
-    let mut result = 0;
-    for (_, count) in responses.iter() {
-        if *count == group_size {
-            result += 1;
-        }
-    }
-    result
+    reader.lines().map(|l| l.unwrap()).collect()
 }
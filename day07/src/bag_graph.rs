@@ -0,0 +1,281 @@
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+/// Represents a bag with its rules
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bag {
+    pub bag_description: String,
+    pub contents: Option<Vec<BagRule>>,
+}
+
+/// Represents a rule that a bag must conform to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BagRule {
+    pub description: String,
+    pub number: u32,
+}
+
+/// Interpret the rule of the form '[bag description] contains [number] [description] bag[s], ...
+pub fn interpret_rule(rule: &str) -> Bag {
+    // Remove all words bag, bags and full stops
+    let stripped = rule.replace(" bags", " ").replace(" bag", " ").replace('.', "");
+    // First we will split on the word 'contains'
+    let temp: Vec<&str> = stripped.split("contain").collect();
+    let bag_description = String::from(temp[0].trim());
+    let contents_unparsed = temp[1].trim().split(',');
+    let mut holds: Vec<BagRule> = Vec::new();
+    for bag in contents_unparsed {
+        // Ignore bags that contain no other bag
+        if bag == "no other" {
+            break
+        }
+        let words: Vec<&str> = bag.split_whitespace().collect();
+        let number = words[0].parse::<u32>().unwrap();
+        let mut description: String = String::from(words[1]);
+        description.push(' ');
+        description.push_str(words[2]);
+        holds.push(BagRule { description, number, })
+    }
+
+    Bag {
+        bag_description,
+        contents: if holds.is_empty() { None } else { Some(holds) },
+    }
+}
+
+/// Problems found when querying the bag graph
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BagError {
+    /// No bag with this description appears in the rules
+    UnknownBag(String),
+    /// The rules contain a cycle - each bag holds the next and the last holds the first
+    Cycle(Vec<String>),
+    /// Bags which are mentioned but have no rule of their own
+    MissingRules(Vec<String>),
+    /// Counting the contents of this bag overflowed
+    Overflow(String),
+}
+
+impl fmt::Display for BagError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BagError::UnknownBag(bag) => write!(f, "no rule mentions a {} bag", bag),
+            BagError::Cycle(bags) => write!(f, "cyclic rules: {} -> {}", bags.join(" -> "), bags[0]),
+            BagError::MissingRules(bags) => write!(f, "no rule for {}", bags.join(", ")),
+            BagError::Overflow(bag) => write!(f, "too many bags inside {} to count", bag),
+        }
+    }
+}
+
+/// The luggage rules as a graph - an edge from each bag to each bag it must directly contain.
+/// Bags are stored by index with adjacency lists in both directions.
+#[derive(Debug, Clone, Default)]
+pub struct BagGraph {
+    pub(crate) descriptions: Vec<String>,
+    pub(crate) index: HashMap<String, usize>,
+    /// Whether each bag has a rule of its own (rather than only being mentioned in another bag's rule)
+    pub(crate) has_rule: Vec<bool>,
+    /// The bags each bag directly contains, with how many
+    pub(crate) contents: Vec<Vec<(usize, u32)>>,
+    /// The bags which directly contain each bag, with how many
+    pub(crate) containers: Vec<Vec<(usize, u32)>>,
+}
+
+impl BagGraph {
+    /// Build the graph from interpreted rules
+    ///
+    /// # Arguments
+    ///
+    /// * `bags` the bags and their rules
+    pub fn from_bags<I: IntoIterator<Item = Bag>>(bags: I) -> Self {
+        let mut graph = BagGraph::default();
+        for bag in bags {
+            let outer = graph.add_bag(&bag.bag_description);
+            graph.has_rule[outer] = true;
+            for rule in bag.contents.unwrap_or_default() {
+                let inner = graph.add_bag(&rule.description);
+                graph.contents[outer].push((inner, rule.number));
+                graph.containers[inner].push((outer, rule.number));
+            }
+        }
+        graph
+    }
+
+    /// Read and interpret the rules file, one rule per line
+    ///
+    /// # Arguments
+    ///
+    /// * `filename` the rules file
+    pub fn load(filename: &str) -> Self {
+        let file = File::open(filename).unwrap();
+        let reader = BufReader::new(file);
+        BagGraph::from_bags(reader.lines()
+            .map(|l| l.unwrap())
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| interpret_rule(&l)))
+    }
+
+    /// Get the index of a bag, adding it if it hasn't been seen
+    fn add_bag(&mut self, description: &str) -> usize {
+        if let Some(i) = self.index.get(description) {
+            return *i;
+        }
+        let i = self.descriptions.len();
+        self.descriptions.push(description.to_string());
+        self.index.insert(description.to_string(), i);
+        self.has_rule.push(false);
+        self.contents.push(Vec::new());
+        self.containers.push(Vec::new());
+        i
+    }
+
+    pub(crate) fn find(&self, description: &str) -> Result<usize, BagError> {
+        self.index.get(description).copied().ok_or_else(|| BagError::UnknownBag(description.to_string()))
+    }
+
+    /// Number of distinct bags mentioned in the rules
+    pub fn len(&self) -> usize {
+        self.descriptions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.descriptions.is_empty()
+    }
+
+    /// Every bag description in the order first mentioned
+    pub fn bags(&self) -> impl Iterator<Item = &str> {
+        self.descriptions.iter().map(|d| d.as_str())
+    }
+
+    /// Whether the bag has a rule of its own
+    pub fn has_rule(&self, description: &str) -> bool {
+        self.index.get(description).map(|i| self.has_rule[*i]).unwrap_or(false)
+    }
+
+    /// The bags directly inside a bag, with how many of each
+    pub fn contents(&self, description: &str) -> Result<Vec<(&str, u32)>, BagError> {
+        let i = self.find(description)?;
+        Ok(self.contents[i].iter().map(|(j, n)| (self.descriptions[*j].as_str(), *n)).collect())
+    }
+
+    /// The bags which directly hold a bag, with how many of it they hold
+    pub fn containers(&self, description: &str) -> Result<Vec<(&str, u32)>, BagError> {
+        let i = self.find(description)?;
+        Ok(self.containers[i].iter().map(|(j, n)| (self.descriptions[*j].as_str(), *n)).collect())
+    }
+
+    /// Breadth first search from a bag along one set of adjacency lists (excluding the starting bag)
+    pub(crate) fn reachable(&self, start: usize, edges: &[Vec<(usize, u32)>]) -> Vec<bool> {
+        let mut seen = vec![false; self.len()];
+        let mut queue: VecDeque<usize> = edges[start].iter().map(|(j, _)| *j).collect();
+        while let Some(i) = queue.pop_front() {
+            if !seen[i] {
+                seen[i] = true;
+                queue.extend(edges[i].iter().map(|(j, _)| *j));
+            }
+        }
+        seen
+    }
+
+    fn collect(&self, selected: Vec<bool>) -> BTreeSet<&str> {
+        selected.iter().enumerate().filter(|(_, s)| **s).map(|(i, _)| self.descriptions[i].as_str()).collect()
+    }
+
+    /// Every bag which can eventually hold a bag
+    pub fn eventual_containers(&self, description: &str) -> Result<BTreeSet<&str>, BagError> {
+        let i = self.find(description)?;
+        Ok(self.collect(self.reachable(i, &self.containers)))
+    }
+
+    /// Every kind of bag which ends up inside a bag
+    pub fn eventual_contents(&self, description: &str) -> Result<BTreeSet<&str>, BagError> {
+        let i = self.find(description)?;
+        Ok(self.collect(self.reachable(i, &self.contents)))
+    }
+
+    /// Find a cycle in the rules (which would make a bag contain itself)
+    ///
+    /// # Returns
+    ///
+    /// * The bags making up the cycle, each containing the next, or None if the rules are acyclic
+    pub fn find_cycle(&self) -> Option<Vec<String>> {
+        self.post_order(0..self.len()).err()
+    }
+
+    /// Depth first search along the contents from some bags, visiting only the bags they end up holding
+    ///
+    /// # Arguments
+    ///
+    /// * `roots` the bags to start from
+    ///
+    /// # Returns
+    ///
+    /// * The bags visited with every bag after all the bags it contains, or the bags making up a cycle
+    ///   if one is reachable (each containing the next)
+    pub(crate) fn post_order<I: IntoIterator<Item = usize>>(&self, roots: I) -> Result<Vec<usize>, Vec<String>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum State { Unvisited, OnPath, Done }
+        let mut state = vec![State::Unvisited; self.len()];
+        let mut order = Vec::new();
+
+        for root in roots {
+            if state[root] != State::Unvisited {
+                continue;
+            }
+            // Iterative depth first search - the path holds each bag and the next of its contents to visit
+            let mut path: Vec<(usize, usize)> = vec![(root, 0)];
+            state[root] = State::OnPath;
+            while let Some((bag, next)) = path.last_mut() {
+                let bag = *bag;
+                match self.contents[bag].get(*next) {
+                    Some((inner, _)) => {
+                        *next += 1;
+                        match state[*inner] {
+                            State::Unvisited => {
+                                state[*inner] = State::OnPath;
+                                path.push((*inner, 0));
+                            }
+                            State::OnPath => {
+                                let start = path.iter().position(|(b, _)| b == inner).unwrap();
+                                return Err(path[start..].iter().map(|(b, _)| self.descriptions[*b].clone()).collect());
+                            }
+                            State::Done => {}
+                        }
+                    }
+                    None => {
+                        state[bag] = State::Done;
+                        order.push(bag);
+                        path.pop();
+                    }
+                }
+            }
+        }
+        Ok(order)
+    }
+
+    /// Order the bags so every bag comes before all the bags it contains
+    ///
+    /// # Returns
+    ///
+    /// * The ordered bags, or the cycle preventing an ordering
+    pub fn topological_order(&self) -> Result<Vec<&str>, BagError> {
+        let mut holders: Vec<usize> = self.containers.iter().map(|c| c.len()).collect();
+        let mut ready: VecDeque<usize> = (0..self.len()).filter(|i| holders[*i] == 0).collect();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(i) = ready.pop_front() {
+            order.push(self.descriptions[i].as_str());
+            for (j, _) in &self.contents[i] {
+                holders[*j] -= 1;
+                if holders[*j] == 0 {
+                    ready.push_back(*j);
+                }
+            }
+        }
+        if order.len() < self.len() {
+            return Err(BagError::Cycle(self.find_cycle().unwrap()));
+        }
+        Ok(order)
+    }
+}
@@ -0,0 +1,117 @@
+use std::fmt::{Debug, Display};
+
+use crate::bag_graph::{BagError, BagGraph};
+
+/// Unsigned integer types bags can be counted in
+pub trait BagCount: Copy + Debug + Display + PartialEq {
+    fn zero() -> Self;
+    fn from_u32(n: u32) -> Self;
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_bag_count {
+    ($($t:ty),*) => {
+        $(
+            impl BagCount for $t {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn from_u32(n: u32) -> Self {
+                    n as $t
+                }
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_bag_count!(u64, u128);
+
+impl BagGraph {
+    /// Check a bag's contents can be counted - no bag it ends up holding contains itself
+    /// and every one has a rule. Rules for bags which never end up inside it don't matter.
+    ///
+    /// # Returns
+    ///
+    /// * The bag and every bag inside it, with each after all the bags it contains (so the bag itself is last)
+    fn countable(&self, description: &str) -> Result<Vec<usize>, BagError> {
+        let i = self.find(description)?;
+        let order = self.post_order(Some(i)).map_err(BagError::Cycle)?;
+        let missing: Vec<String> = order.iter()
+            .filter(|j| !self.has_rule[**j])
+            .map(|j| self.descriptions[*j].clone())
+            .collect();
+        if !missing.is_empty() {
+            return Err(BagError::MissingRules(missing));
+        }
+        Ok(order)
+    }
+
+    /// Total number of bags inside a bag (not counting the bag itself).
+    /// Each bag's total is worked out once, innermost bags first, and reused by every bag holding it.
+    ///
+    /// # Arguments
+    ///
+    /// * `description` the outer bag
+    ///
+    /// # Returns
+    ///
+    /// * The count, or an error if the bag is unknown, the rules are cyclic, a bag inside has
+    ///   no rule or the count overflows `T`
+    pub fn count_contents<T: BagCount>(&self, description: &str) -> Result<T, BagError> {
+        let order = self.countable(description)?;
+        let overflow = || BagError::Overflow(description.to_string());
+
+        let mut totals: Vec<Option<T>> = vec![None; self.len()];
+        for j in &order {
+            let mut total = T::zero();
+            for (k, n) in &self.contents[*j] {
+                // n of the inner bag, plus n lots of everything inside it
+                let each = totals[*k].unwrap().checked_add(T::from_u32(1)).ok_or_else(overflow)?;
+                total = total.checked_add(each.checked_mul(T::from_u32(*n)).ok_or_else(overflow)?).ok_or_else(overflow)?;
+            }
+            totals[*j] = Some(total);
+        }
+        Ok(totals[*order.last().unwrap()].unwrap())
+    }
+
+    /// How many of each kind of bag end up inside a bag
+    ///
+    /// # Arguments
+    ///
+    /// * `description` the outer bag
+    ///
+    /// # Returns
+    ///
+    /// * Each bag inside with its count, outermost first, or an error as for `count_contents`
+    pub fn contents_breakdown<T: BagCount>(&self, description: &str) -> Result<Vec<(&str, T)>, BagError> {
+        let mut order = self.countable(description)?;
+        order.reverse();
+        let i = order[0];
+        let overflow = || BagError::Overflow(description.to_string());
+
+        // Push the number of copies of each bag down to the bags it holds, outermost first
+        let mut copies: Vec<T> = vec![T::zero(); self.len()];
+        copies[i] = T::from_u32(1);
+        for j in &order {
+            for (k, n) in &self.contents[*j] {
+                copies[*k] = copies[*j].checked_mul(T::from_u32(*n))
+                    .and_then(|c| copies[*k].checked_add(c))
+                    .ok_or_else(overflow)?;
+            }
+        }
+        Ok(order.into_iter()
+            .filter(|j| *j != i)
+            .map(|j| (self.descriptions[j].as_str(), copies[j]))
+            .collect())
+    }
+}
@@ -0,0 +1,97 @@
+use std::fmt::Write;
+
+use crate::bag_graph::{BagError, BagGraph};
+
+/// Which bags to highlight around the chosen bag
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reach {
+    /// Bags which end up inside the chosen bag
+    Contents,
+    /// Bags which can eventually hold the chosen bag
+    Containers,
+    /// Both of the above
+    Both,
+}
+
+impl Reach {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "contents" => Ok(Reach::Contents),
+            "containers" => Ok(Reach::Containers),
+            "both" => Ok(Reach::Both),
+            _ => Err(format!("unknown direction \"{}\" (expected contents, containers or both)", s)),
+        }
+    }
+}
+
+impl BagGraph {
+    /// Export the rules as a Graphviz DOT digraph with an edge from each bag to each bag it
+    /// directly holds, labelled with how many it holds
+    ///
+    /// # Arguments
+    ///
+    /// * `highlight` a bag to highlight along with the bags reachable from it in the given direction
+    /// * `only_highlighted` leave out the bags and rules that aren't highlighted
+    pub fn to_dot(&self, highlight: Option<(&str, Reach)>, only_highlighted: bool) -> Result<String, BagError> {
+        // Bags above (holding) and below (inside) the highlighted bag - the bag itself is in both
+        let mut above = vec![highlight.is_none(); self.len()];
+        let mut below = above.clone();
+        let mut chosen = None;
+        if let Some((bag, reach)) = highlight {
+            let i = self.find(bag)?;
+            chosen = Some(i);
+            if reach != Reach::Contents {
+                above = self.reachable(i, &self.containers);
+            }
+            if reach != Reach::Containers {
+                below = self.reachable(i, &self.contents);
+            }
+            above[i] = true;
+            below[i] = true;
+        }
+        let highlighted = |i: usize| above[i] || below[i];
+        let edge_highlighted = |i: usize, j: usize| (above[i] && above[j]) || (below[i] && below[j]);
+        let dimmed = highlight.is_some();
+
+        let mut out = String::from("digraph luggage {\n    rankdir=LR;\n    node [shape=box, style=rounded];\n");
+        for (i, description) in self.descriptions.iter().enumerate() {
+            if only_highlighted && !highlighted(i) {
+                continue;
+            }
+            let style = if Some(i) == chosen {
+                " [style=\"rounded,filled\", fillcolor=gold, penwidth=2]"
+            } else if dimmed && highlighted(i) {
+                " [style=\"rounded,filled\", fillcolor=lightblue]"
+            } else if dimmed {
+                " [color=gray, fontcolor=gray]"
+            } else {
+                ""
+            };
+            writeln!(out, "    {}{};", dot_id(description), style).unwrap();
+        }
+        for (i, contents) in self.contents.iter().enumerate() {
+            for (j, number) in contents {
+                let on_path = edge_highlighted(i, *j);
+                if only_highlighted && !on_path {
+                    continue;
+                }
+                let style = if dimmed && on_path {
+                    ", color=blue, penwidth=2"
+                } else if dimmed {
+                    ", color=gray, fontcolor=gray"
+                } else {
+                    ""
+                };
+                writeln!(out, "    {} -> {} [label=\"{}\"{}];",
+                         dot_id(&self.descriptions[i]), dot_id(&self.descriptions[*j]), number, style).unwrap();
+            }
+        }
+        out.push_str("}\n");
+        Ok(out)
+    }
+}
+
+/// Quote a bag description as a DOT identifier
+fn dot_id(description: &str) -> String {
+    format!("\"{}\"", description.replace('\\', "\\\\").replace('"', "\\\""))
+}
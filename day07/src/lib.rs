@@ -0,0 +1,8 @@
+pub mod bag_graph;
+pub use bag_graph::*;
+
+pub mod counting;
+pub use counting::*;
+
+pub mod dot;
+pub use dot::*;
@@ -1,4 +1,4 @@
-/**
+/*
  * --- Day 7: Handy Haversacks ---
  * 
  * You land at the regional airport in time for your next flight. In fact, it looks like you'll even have time to grab some food: all flights are currently delayed due to issues in luggage processing.
@@ -56,138 +56,76 @@
  * How many individual bags are required inside your single shiny gold bag?
  */
 
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::collections::HashMap;
+use std::env;
+use std::fs;
 
-fn main() {
-
-    let mut search_terms = vec!(String::from("shiny gold"));
-    let mut results = Vec::new();
-    loop {
-        let rules = read_in_file("src/bagrules.txt");
-        let mut bags: Vec<Bag> = Vec::new();
-        for rule in rules {
-            bags.push( interpret_rule(&rule) );
-        }
-    
-        search_terms = search_bags(bags, &search_terms);
+use luggage::{BagGraph, Reach};
 
-        if search_terms.len() == 0 {
-            break;
-        }
-
-        for bag in search_terms.iter() {
-            if !results.contains(bag) {
-                results.push(bag.clone());
+fn main() {
+    // Optional arguments:
+    //   <bag>              the bag to search from (default "shiny gold")
+    //   order              also list every bag, outermost first
+    //   breakdown          also list how many of each bag end up inside the bag
+    //   dot <file> [reach] write the rules as a Graphviz DOT file, highlighting the bags reachable from
+    //                      the bag - reach is contents, containers or both (default both)
+    //   only               with dot, leave out the bags that aren't highlighted
+    let mut target = String::from("shiny gold");
+    let (mut order, mut breakdown, mut only) = (false, false, false);
+    let mut dot: Option<(String, Reach)> = None;
+    let mut args = env::args().skip(1).peekable();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "order" => order = true,
+            "breakdown" => breakdown = true,
+            "only" => only = true,
+            "dot" => {
+                let file = args.next().expect("dot needs a file name");
+                let reach = match args.peek().map(|a| Reach::parse(a)) {
+                    Some(Ok(reach)) => {
+                        args.next();
+                        reach
+                    }
+                    _ => Reach::Both,
+                };
+                dot = Some((file, reach));
             }
+            _ => target = arg,
         }
-        println!("RESULTS: {:?}", results.len());
     }
 
-    // Traverse the map...
-    let rules = read_in_file("src/bagrules.txt");
-    let mut bags: HashMap<String, Bag> = HashMap::new();
-    for rule in rules {
-        let bag = interpret_rule(&rule);
-        bags.insert(bag.bag_description.clone(), bag);
+    let graph = BagGraph::load("src/bagrules.txt");
+    // Written before checking for cycles so the file can be used to find them
+    if let Some((file, reach)) = &dot {
+        fs::write(file, graph.to_dot(Some((&target, *reach)), only).unwrap()).unwrap();
+        println!("Written {}", file);
     }
-
-    // -1 beacause the calculation includes the gold bag
-    println!("The shiny gold bag must contain {} bags", count_bag_contents(&bags, String::from("shiny gold")) - 1);
-}
-
-/// Read in lines of a file to a vector
-/// 
-/// # Arguments
-/// 
-/// * `filename` - String containing the filename
-fn read_in_file(filename: &str) -> Vec<String> {
-    // Open the file in read-only mode (ignoring errors).
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
-
-    reader.lines().flatten().collect()
-}
-
-/// Represents a bag with its rules
-#[derive(Debug)]
-struct Bag {
-    bag_description: String,
-    contents: Option< Vec<BagRule> >,
-}
-
-/// Represents a rule that a bag must conform to
-#[derive(Debug)]
-struct BagRule {
-    description: String,
-    number: u32
-}
-
-/// Interpret the rule of the form '[bag description] contains [number] [description] bag[s], ...
-fn interpret_rule(rule: &str) -> Bag {
-    // Rule is of the form '[bag description] contains [number] [description] bag[s], ...
-    
-    // Remove all words bag, bags and full stops
-    let stripped = rule.replace(" bags", " ").replace(" bag", " ").replace(".", "");
-    // First we will split on the word 'contains'
-    let temp: Vec<&str> = stripped.split("contain").collect();
-    let bag_description = String::from(temp[0].trim());
-    let contents_unparsed = temp[1].trim().split(',');
-    let mut holds: Vec<BagRule> = Vec::new();
-    for bag in contents_unparsed {
-        // Ignore bags that contain no other bag
-        if bag == "no other" {
-            break
-        }
-        let words: Vec<&str> = bag.split_whitespace().collect();
-        let number = words[0].parse::<u32>().unwrap();
-        let mut description: String = String::from(words[1]);
-        description.push_str(" ");
-        description.push_str(words[2]);
-        holds.push(BagRule { description, number, })
+    if let Some(cycle) = graph.find_cycle() {
+        println!("Warning: {} -> {} contain each other", cycle.join(" -> "), cycle[0]);
     }
 
-    if holds.len() == 0 {
-        Bag {
-            bag_description,
-            contents: None,
-        }
-    } else {
-        Bag {
-            bag_description,
-            contents: Some(holds),
+    if order {
+        match graph.topological_order() {
+            Ok(bags) => for bag in bags {
+                let contents: Vec<String> = graph.contents(bag).unwrap().iter().map(|(b, n)| format!("{} {}", n, b)).collect();
+                println!("{}: {}", bag, if contents.is_empty() { "no other bags".to_string() } else { contents.join(", ") });
+            },
+            Err(e) => println!("Can't order the bags: {}", e),
         }
     }
-    // println!("DESC: {}, CONTENTS: {:?}", description, contents);
-}
-
-/// Return a list of bags that contain those in the search_items
-fn search_bags(bags: Vec<Bag>, search_items: &Vec<String>) -> Vec<String> {
-    let mut result = Vec::new();
 
-    for bag in bags {
-        if bag.contents.is_some() {
-            for rule in bag.contents.unwrap() {
-                if search_items.contains(&rule.description) {
-                    if !result.contains(&bag.bag_description) {
-                        result.push(bag.bag_description);
-                        break;
-                    }
-                }
-            }
-        }
+    let containers = graph.eventual_containers(&target).unwrap();
+    println!("{} bags can eventually contain a {} bag", containers.len(), target);
+    match graph.count_contents::<u128>(&target) {
+        Ok(count) => println!("The {} bag must contain {} bags", target, count),
+        Err(e) => println!("Can't count the bags inside {}: {}", target, e),
     }
-    result
-}
 
-/// Recursive search of a bag map to find the number of bags contained
-fn count_bag_contents(bags: &HashMap<String, Bag>, bag: String) -> u32 {
-    let mut total = 1;
-    if let Some(bag_contents) = &bags.get(&bag).unwrap().contents {
-        for item in bag_contents {
-            total += item.number * count_bag_contents(bags, item.description.clone());
+    if breakdown {
+        match graph.contents_breakdown::<u128>(&target) {
+            Ok(bags) => for (bag, count) in bags {
+                println!("{:>8} {}", count, bag);
+            },
+            Err(e) => println!("Can't break down the bags inside {}: {}", target, e),
         }
     }
-    total
 }
@@ -1,4 +1,4 @@
-/**
+/*
  * --- Day 9: Encoding Error ---
  * 
  * With your neighbor happily enjoying their video game, you turn your attention to an open data port on the little screen in the seat in front of you.
@@ -76,37 +76,49 @@
  * What is the encryption weakness in your XMAS-encrypted list of numbers?
  */
 
+mod weakness;
+mod xmas;
+
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 
+use weakness::contiguous_ranges;
+use xmas::XmasValidator;
+
 fn main() {
+    // Optional arguments:
+    //   <preamble>    the number of previous values each value must be a sum of two of (default 25)
+    //   <min length>  the fewest values a range summing to the invalid number may have (default 2)
+    let preamble = std::env::args().nth(1).map(|p| p.parse::<usize>().unwrap()).unwrap_or(25);
+    let min_length = std::env::args().nth(2).map(|p| p.parse::<usize>().unwrap()).unwrap_or(2);
+
     let filename = "src/input.txt";
     let lines = read_in_lines(filename);
 
-    // Read the file line by line using the lines() iterator from std::io::BufRead.
-    let mut values = Vec::new();
-    for line in lines {
-        values.push(line.parse::<u64>().unwrap());
-    }
-
+    let values: Vec<u64> = lines.iter().map(|line| line.parse::<u64>().unwrap()).collect();
 
-    let mut search_value = 0;
-    let mut temp_vector = Vec::new();
-    for x in values.clone() {
-        temp_vector.insert(0, x);
-        if temp_vector.len() < 27 {
-            continue
-        }
-        if !find_sum_from_two_values(temp_vector[0], &temp_vector[1..]) {
-            println!("--- Part 1 ---\nAnswer: {}", temp_vector[0]);
-            search_value = temp_vector[0];
-        }
+    let invalid: Vec<_> = XmasValidator::new(values.iter().copied(), preamble).collect();
+    for number in &invalid {
+        println!("Value {} at position {} is not the sum of two of the previous {}", number.value, number.position, preamble);
     }
+    let search_value = match invalid.first() {
+        Some(number) => number.value,
+        None => {
+            println!("Every value is valid");
+            return;
+        }
+    };
+    println!("--- Part 1 ---\nAnswer: {}", search_value);
 
-    let mut result = find_sum_from_contiguous_values(search_value, values.clone());
-    result.sort();
-
-    println!("--- Part 2 ---\nmin: {}, max: {}, answer: {}", result[0], result[result.len()-1], result[0] + result[result.len()-1]);
+    println!("--- Part 2 ---");
+    let ranges = contiguous_ranges(&values, search_value, min_length);
+    if ranges.is_empty() {
+        println!("No range of at least {} values sums to {}", min_length, search_value);
+    }
+    for range in ranges {
+        println!("positions {}..{} ({} values) min: {}, max: {}, answer: {}",
+                 range.start, range.end, range.len(), range.min, range.max, range.weakness());
+    }
 }
 
 
@@ -120,44 +132,5 @@ fn read_in_lines(filename: &str) -> Vec<String> {
     let file = File::open(filename).unwrap();
     let reader = BufReader::new(file);
 
-    reader.lines().flatten().collect()
+    reader.lines().map(|l| l.unwrap()).collect()
 }
-
-/// Find two values in the passed vector that add to the target value
-/// 
-/// # Arguments
-/// 
-/// * `target` - The target value
-/// * `sroted_values` - A vector containing the search space
-fn find_sum_from_two_values(target: u64, values: &[u64]) -> bool {
-    // Iterate over all numbers and find the two that sum to target value
-    // We can break once sum is greater than target because numbers are
-    // ordered smallest to largest
-    for (i, x) in values[0..].iter().enumerate() {
-        for (_, y) in values[i..].iter().enumerate() {
-            if x + y  == target {
-                return true
-            }
-        }
-    }
-    false
-}
-
-fn find_sum_from_contiguous_values(target: u64, values: Vec<u64>) -> Vec<u64> {
-    let mut search = Vec::new();
-    let mut sum = 0;
-    for x in values {
-        search.insert(0, x);
-        sum += x;
-        while sum > target {
-            sum -= search.pop().unwrap();
-            if search.len() == 0 {
-                break;
-            }
-        }
-        if sum == target && search.len() > 1 {
-            break;
-        }
-    }
-    return search;
-}
\ No newline at end of file
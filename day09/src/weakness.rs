@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+
+/// A run of consecutive values summing to a target
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContiguousRange {
+    /// Position of the first value
+    pub start: usize,
+    /// Position after the last value
+    pub end: usize,
+    pub min: u64,
+    pub max: u64,
+}
+
+impl ContiguousRange {
+    /// Number of values in the range
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// The encryption weakness - the sum of the smallest and largest values in the range
+    pub fn weakness(&self) -> u64 {
+        self.min + self.max
+    }
+}
+
+/// Find every run of consecutive values summing to a target.
+///
+/// Uses prefix sums - a range from i to j sums to the target when prefix[j] - prefix[i] == target, so
+/// each end position is a lookup of the earlier prefix sums equal to prefix[j] - target.
+///
+/// # Arguments
+///
+/// * `values` the values to search
+/// * `target` the sum to find
+/// * `min_length` the smallest number of values a range may have (at least 1)
+///
+/// # Returns
+///
+/// * Every matching range, ordered by end then start - empty if there are none
+pub fn contiguous_ranges(values: &[u64], target: u64, min_length: usize) -> Vec<ContiguousRange> {
+    let min_length = min_length.max(1);
+    let mut ranges = Vec::new();
+    // Positions with each prefix sum (several if there are zeros)
+    let mut starts: HashMap<u128, Vec<usize>> = HashMap::new();
+    let mut prefix: u128 = 0;
+    starts.entry(0).or_default().push(0);
+
+    for (i, value) in values.iter().enumerate() {
+        prefix += *value as u128;
+        let end = i + 1;
+        if let Some(positions) = prefix.checked_sub(target as u128).and_then(|p| starts.get(&p)) {
+            for start in positions.iter().filter(|s| end - **s >= min_length) {
+                let range = &values[*start..end];
+                ranges.push(ContiguousRange {
+                    start: *start,
+                    end,
+                    min: *range.iter().min().unwrap(),
+                    max: *range.iter().max().unwrap(),
+                });
+            }
+        }
+        starts.entry(prefix).or_default().push(end);
+    }
+    ranges
+}
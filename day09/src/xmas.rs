@@ -0,0 +1,77 @@
+use std::collections::{HashMap, VecDeque};
+
+/// A number which isn't the sum of two different numbers in the window before it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidNumber {
+    /// Position of the number in the input (starting at 0)
+    pub position: usize,
+    pub value: u64,
+}
+
+/// Checks a stream of XMAS numbers, yielding each number (after the preamble) which isn't the
+/// sum of two numbers with different values among the `preamble` numbers before it.
+///
+/// The window is kept in a queue along with a count of each value in it, so checking a number is
+/// a hash lookup for each value in the window.
+#[derive(Debug, Clone)]
+pub struct XmasValidator<I> {
+    numbers: I,
+    preamble: usize,
+    window: VecDeque<u64>,
+    counts: HashMap<u64, usize>,
+    position: usize,
+}
+
+impl<I: Iterator<Item = u64>> XmasValidator<I> {
+    /// Create a new validator
+    ///
+    /// # Arguments
+    ///
+    /// * `numbers` the numbers to check
+    /// * `preamble` the number of previous numbers a number must be the sum of two of
+    pub fn new<N: IntoIterator<IntoIter = I>>(numbers: N, preamble: usize) -> Self {
+        XmasValidator {
+            numbers: numbers.into_iter(),
+            preamble,
+            window: VecDeque::with_capacity(preamble + 1),
+            counts: HashMap::new(),
+            position: 0,
+        }
+    }
+
+    /// Whether the value is the sum of two different values in the window
+    fn is_valid(&self, value: u64) -> bool {
+        self.window.iter().any(|w| *w <= value && value - w != *w && self.counts.contains_key(&(value - w)))
+    }
+
+    /// Add a value to the window, dropping the oldest once it is full
+    fn push(&mut self, value: u64) {
+        self.window.push_back(value);
+        *self.counts.entry(value).or_insert(0) += 1;
+        if self.window.len() > self.preamble {
+            let old = self.window.pop_front().unwrap();
+            let count = self.counts.get_mut(&old).unwrap();
+            *count -= 1;
+            if *count == 0 {
+                self.counts.remove(&old);
+            }
+        }
+    }
+}
+
+impl<I: Iterator<Item = u64>> Iterator for XmasValidator<I> {
+    type Item = InvalidNumber;
+
+    fn next(&mut self) -> Option<InvalidNumber> {
+        while let Some(value) = self.numbers.next() {
+            let position = self.position;
+            self.position += 1;
+            let invalid = position >= self.preamble && !self.is_valid(value);
+            self.push(value);
+            if invalid {
+                return Some(InvalidNumber { position, value });
+            }
+        }
+        None
+    }
+}
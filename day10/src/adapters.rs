@@ -0,0 +1,124 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use crate::biguint::BigUint;
+
+pub type Adapter = u32;
+
+/// Problems with a set of adapters
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainError {
+    /// Joining every adapter in order needs a jump outside the allowed range between these ratings
+    InvalidGap { from: Adapter, to: Adapter },
+    /// There is no way to get from the socket to the device
+    NoArrangement,
+}
+
+impl fmt::Display for ChainError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ChainError::InvalidGap { from, to } =>
+                write!(f, "a {} jolt adapter can't connect to a {} jolt adapter", from, to),
+            ChainError::NoArrangement => write!(f, "no arrangement of adapters connects the socket to the device"),
+        }
+    }
+}
+
+/// The joltage adapters along with the outlet and the device, sorted by rating
+#[derive(Debug, Clone)]
+pub struct AdapterChain {
+    /// Ratings in order - the outlet (0) first and the device last
+    ratings: Vec<Adapter>,
+    /// The allowed differences between connected adapters
+    jumps: RangeInclusive<Adapter>,
+}
+
+impl AdapterChain {
+    /// Create a chain from a bag of adapters. The device is rated the largest jump above the highest adapter.
+    ///
+    /// # Arguments
+    ///
+    /// * `adapters` the adapter ratings in any order
+    /// * `jumps` the allowed differences in rating between connected adapters, e.g. 1..=3
+    pub fn new(adapters: &[Adapter], jumps: RangeInclusive<Adapter>) -> Self {
+        let mut ratings = Vec::with_capacity(adapters.len() + 2);
+        ratings.push(0);
+        ratings.extend_from_slice(adapters);
+        ratings.sort_unstable();
+        ratings.push(ratings.last().unwrap() + jumps.end());
+        AdapterChain { ratings, jumps }
+    }
+
+    /// Every rating in order including the outlet and the device
+    pub fn ratings(&self) -> &[Adapter] {
+        &self.ratings
+    }
+
+    /// The device's rating
+    pub fn device(&self) -> Adapter {
+        *self.ratings.last().unwrap()
+    }
+
+    /// Whether an adapter at index `from` can connect to the one at index `to`
+    pub(crate) fn connects(&self, from: usize, to: usize) -> bool {
+        self.jumps.contains(&(self.ratings[to] - self.ratings[from]))
+    }
+
+    /// The indices of the adapters the adapter at index `from` can connect to
+    pub(crate) fn connections(&self, from: usize) -> impl Iterator<Item = usize> + '_ {
+        let end = self.ratings[from] + self.jumps.end();
+        (from + 1..self.ratings.len())
+            .take_while(move |to| self.ratings[*to] <= end)
+            .filter(move |to| self.connects(from, *to))
+    }
+
+    /// Count how many times each difference occurs when every adapter is used
+    ///
+    /// # Returns
+    ///
+    /// * The number of jumps of each size, or the first pair of adapters which can't be connected
+    pub fn differences(&self) -> Result<BTreeMap<Adapter, usize>, ChainError> {
+        let mut histogram = BTreeMap::new();
+        for i in 1..self.ratings.len() {
+            if !self.connects(i - 1, i) {
+                return Err(ChainError::InvalidGap { from: self.ratings[i - 1], to: self.ratings[i] });
+            }
+            *histogram.entry(self.ratings[i] - self.ratings[i - 1]).or_insert(0) += 1;
+        }
+        Ok(histogram)
+    }
+
+    /// The number of arrangements from each adapter to the device (indexed as `ratings`)
+    pub(crate) fn arrangements_from(&self) -> Vec<BigUint> {
+        let mut counts = vec![BigUint::zero(); self.ratings.len()];
+        let last = self.ratings.len() - 1;
+        counts[last] = BigUint::one();
+        for i in (0..last).rev() {
+            let mut total = BigUint::zero();
+            for j in self.connections(i) {
+                total += &counts[j];
+            }
+            counts[i] = total;
+        }
+        counts
+    }
+
+    /// Count the distinct arrangements of adapters connecting the outlet to the device
+    ///
+    /// # Returns
+    ///
+    /// * The count, or an error if there is no arrangement - naming the gap if one can't be jumped
+    pub fn arrangements(&self) -> Result<BigUint, ChainError> {
+        let count = self.arrangements_from().swap_remove(0);
+        if count.is_zero() {
+            for i in 1..self.ratings.len() {
+                if self.ratings[i] - self.ratings[i - 1] > *self.jumps.end() {
+                    return Err(ChainError::InvalidGap { from: self.ratings[i - 1], to: self.ratings[i] });
+                }
+            }
+            return Err(ChainError::NoArrangement);
+        }
+        Ok(count)
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::VecDeque;
+
+use crate::adapters::{Adapter, AdapterChain, ChainError};
+
+/// A small xorshift random number generator (not suitable for anything needing real randomness)
+#[derive(Debug, Clone)]
+pub struct XorShift {
+    state: u64,
+}
+
+impl XorShift {
+    /// Create a generator from a seed (any value, zero is replaced)
+    pub fn new(seed: u64) -> Self {
+        XorShift { state: if seed == 0 { 0x9e37_79b9_7f4a_7c15 } else { seed } }
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        (self.state.wrapping_mul(0x2545_f491_4f6c_dd1d) >> 32) as u32
+    }
+}
+
+/// Lazily enumerates every arrangement of adapters from the outlet to the device in
+/// lexicographic order of ratings. Each arrangement includes the outlet and the device.
+pub struct Arrangements<'a> {
+    chain: &'a AdapterChain,
+    /// Whether the device can be reached from each adapter, so dead ends are never explored
+    reaches_device: Vec<bool>,
+    /// The adapters in the current arrangement with the connections still to try from each
+    path: Vec<(usize, Vec<usize>)>,
+}
+
+impl<'a> Arrangements<'a> {
+    fn new(chain: &'a AdapterChain) -> Self {
+        let reaches_device: Vec<bool> = chain.arrangements_from().iter().map(|c| !c.is_zero()).collect();
+        let mut arrangements = Arrangements { chain, reaches_device, path: Vec::new() };
+        if arrangements.reaches_device[0] {
+            let next = arrangements.next_steps(0);
+            arrangements.path.push((0, next));
+        }
+        arrangements
+    }
+
+    /// The connections from an adapter which lead to the device, in reverse so they can be popped in order
+    fn next_steps(&self, from: usize) -> Vec<usize> {
+        let mut steps: Vec<usize> = self.chain.connections(from).filter(|to| self.reaches_device[*to]).collect();
+        steps.reverse();
+        steps
+    }
+}
+
+impl<'a> Iterator for Arrangements<'a> {
+    type Item = Vec<Adapter>;
+
+    fn next(&mut self) -> Option<Vec<Adapter>> {
+        let device = self.chain.ratings().len() - 1;
+        loop {
+            let (_, remaining) = self.path.last_mut()?;
+            match remaining.pop() {
+                Some(next) if next == device => {
+                    let ratings = self.chain.ratings();
+                    let mut arrangement: Vec<Adapter> = self.path.iter().map(|(i, _)| ratings[*i]).collect();
+                    arrangement.push(ratings[device]);
+                    return Some(arrangement);
+                }
+                Some(next) => {
+                    let steps = self.next_steps(next);
+                    self.path.push((next, steps));
+                }
+                None => {
+                    self.path.pop();
+                }
+            }
+        }
+    }
+}
+
+impl AdapterChain {
+    /// Iterate over every arrangement of adapters (see `Arrangements`)
+    pub fn iter_arrangements(&self) -> Arrangements<'_> {
+        Arrangements::new(self)
+    }
+
+    /// Choose an arrangement uniformly at random from all the possible arrangements
+    ///
+    /// # Arguments
+    ///
+    /// * `rng` the random number generator
+    ///
+    /// # Returns
+    ///
+    /// * The ratings in the arrangement (including the outlet and device)
+    pub fn random_arrangement(&self, rng: &mut XorShift) -> Result<Vec<Adapter>, ChainError> {
+        let total = self.arrangements()?;
+        let counts = self.arrangements_from();
+        // Pick the nth arrangement - at each adapter step past the connections whose arrangements are all before n
+        let mut n = total.random_below(|| rng.next_u32());
+        let mut i = 0;
+        let mut arrangement = vec![self.ratings()[0]];
+        while i < self.ratings().len() - 1 {
+            for j in self.connections(i) {
+                if n < counts[j] {
+                    i = j;
+                    break;
+                }
+                n -= &counts[j];
+            }
+            arrangement.push(self.ratings()[i]);
+        }
+        Ok(arrangement)
+    }
+
+    /// Find the arrangement using the fewest adapters
+    ///
+    /// # Returns
+    ///
+    /// * The ratings in the arrangement (including the outlet and device) - if several are equally short the
+    ///   one found first by a breadth first search
+    pub fn fewest_adapters(&self) -> Result<Vec<Adapter>, ChainError> {
+        let last = self.ratings().len() - 1;
+        // Breadth first search from the outlet, recording the adapter each was first reached from
+        let mut previous: Vec<Option<usize>> = vec![None; self.ratings().len()];
+        let mut queue = VecDeque::from(vec![0]);
+        while let Some(i) = queue.pop_front() {
+            if i == last {
+                let mut arrangement = vec![self.ratings()[last]];
+                let mut at = last;
+                while let Some(p) = previous[at] {
+                    arrangement.push(self.ratings()[p]);
+                    at = p;
+                }
+                arrangement.reverse();
+                return Ok(arrangement);
+            }
+            for j in self.connections(i) {
+                if previous[j].is_none() {
+                    previous[j] = Some(i);
+                    queue.push_back(j);
+                }
+            }
+        }
+        // No arrangement - get the reason
+        self.arrangements()?;
+        Err(ChainError::NoArrangement)
+    }
+}
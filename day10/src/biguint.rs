@@ -0,0 +1,152 @@
+use std::fmt;
+use std::cmp::Ordering;
+use std::ops::{Add, AddAssign, SubAssign};
+
+/// An arbitrary precision unsigned integer, stored as base 2^32 limbs (least significant first)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    /// Never has trailing zero limbs, so zero is an empty vector
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    pub fn zero() -> Self {
+        BigUint { limbs: Vec::new() }
+    }
+
+    pub fn one() -> Self {
+        BigUint::from(1)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// Number of bits needed to hold the value
+    pub fn bits(&self) -> usize {
+        match self.limbs.last() {
+            Some(top) => self.limbs.len() * 32 - top.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    /// A uniformly random value from 0 up to (but not including) this value
+    ///
+    /// # Arguments
+    ///
+    /// * `next_u32` a source of uniformly random 32 bit values
+    pub fn random_below<F: FnMut() -> u32>(&self, mut next_u32: F) -> BigUint {
+        assert!(!self.is_zero(), "no values below zero");
+        let bits = self.bits();
+        // Draw values with the same number of bits until one is in range (at least half are)
+        loop {
+            let mut value = BigUint { limbs: (0..bits.div_ceil(32)).map(|_| next_u32()).collect() };
+            if !bits.is_multiple_of(32) {
+                *value.limbs.last_mut().unwrap() &= (1 << (bits % 32)) - 1;
+            }
+            value.trim();
+            if value < *self {
+                return value;
+            }
+        }
+    }
+
+    /// Divide in place by a small number, returning the remainder
+    fn div_rem_small(&mut self, divisor: u32) -> u32 {
+        let mut remainder: u64 = 0;
+        for limb in self.limbs.iter_mut().rev() {
+            let value = (remainder << 32) | *limb as u64;
+            *limb = (value / divisor as u64) as u32;
+            remainder = value % divisor as u64;
+        }
+        self.trim();
+        remainder as u32
+    }
+
+    fn trim(&mut self) {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(n: u64) -> Self {
+        let mut value = BigUint { limbs: vec![n as u32, (n >> 32) as u32] };
+        value.trim();
+        value
+    }
+}
+
+impl AddAssign<&BigUint> for BigUint {
+    fn add_assign(&mut self, other: &BigUint) {
+        if self.limbs.len() < other.limbs.len() {
+            self.limbs.resize(other.limbs.len(), 0);
+        }
+        let mut carry = 0;
+        for (i, limb) in self.limbs.iter_mut().enumerate() {
+            let sum = *limb as u64 + *other.limbs.get(i).unwrap_or(&0) as u64 + carry;
+            *limb = sum as u32;
+            carry = sum >> 32;
+        }
+        if carry > 0 {
+            self.limbs.push(carry as u32);
+        }
+    }
+}
+
+impl SubAssign<&BigUint> for BigUint {
+    /// Panics if `other` is larger
+    fn sub_assign(&mut self, other: &BigUint) {
+        assert!(*self >= *other, "BigUint subtraction underflow");
+        let mut borrow = 0;
+        for (i, limb) in self.limbs.iter_mut().enumerate() {
+            let difference = *limb as i64 - *other.limbs.get(i).unwrap_or(&0) as i64 - borrow;
+            *limb = difference.rem_euclid(1 << 32) as u32;
+            borrow = if difference < 0 { 1 } else { 0 };
+        }
+        self.trim();
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &BigUint) -> Ordering {
+        self.limbs.len().cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &BigUint) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let mut sum = self.clone();
+        sum += other;
+        sum
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        // Split into base 10^9 digits, least significant first
+        let mut value = self.clone();
+        let mut chunks = Vec::new();
+        while !value.is_zero() {
+            chunks.push(value.div_rem_small(1_000_000_000));
+        }
+        write!(f, "{}", chunks.pop().unwrap())?;
+        for chunk in chunks.iter().rev() {
+            write!(f, "{:09}", chunk)?;
+        }
+        Ok(())
+    }
+}
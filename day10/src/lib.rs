@@ -0,0 +1,8 @@
+pub mod biguint;
+pub use biguint::*;
+
+pub mod adapters;
+pub use adapters::*;
+
+pub mod arrangements;
+pub use arrangements::*;
@@ -1,4 +1,4 @@
-/**
+/*
  * --- Day 10: Adapter Array ---
  * 
  * Patched into the aircraft's data port, you discover weather forecasts of a massive tropical storm. Before you can figure out whether it will impact your vacation plans, however, your device suddenly turns off!
@@ -126,68 +126,75 @@
  * What is the total number of distinct ways you can arrange the adapters to connect the charging outlet to your device?
  */
 
+use std::env;
+use std::time::{SystemTime, UNIX_EPOCH};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 
-type Adapter = u32;
+use joltage::{AdapterChain, XorShift};
 
-/// A structure to hold the adapters along with how many others it is able to connect to.
-#[derive(Debug)]
-struct AdapterMap {
-    adapter: Adapter,
-    possible_connections: u64,
-}
-
-/// A structure for storing the differences between the chain of adapters.
-/// The maximum difference considered is three and minimum is one
-#[derive(Debug)]
-struct Differences {
-    one: u32,
-    two: u32,
-    three: u32,
-}
+fn main() {
+    // Optional arguments:
+    //   <min jump> <max jump>  the allowed differences between connected adapters (default 1 3)
+    //   list <n>               show the first n arrangements
+    //   random [seed]          show a random arrangement
+    //   fewest                 show the arrangement using the fewest adapters
+    let args: Vec<String> = env::args().skip(1).collect();
+    let numbers: Vec<u32> = args.iter().take_while(|a| a.parse::<u32>().is_ok()).map(|a| a.parse().unwrap()).collect();
+    let jumps = match numbers.as_slice() {
+        [min, max] => *min..=*max,
+        _ => 1..=3,
+    };
+    let command: Vec<&str> = args.iter().skip(numbers.len()).map(|a| a.as_str()).collect();
 
-impl Differences {
-    /// Create a new Differences struct and fill with 0 values
-    fn new() -> Differences {
-        Differences {
-            one: 0,
-            two: 0,
-            three: 0,
+    let lines = read_in_lines("src/adapter_ratings.txt");
+    let adapters: Vec<u32> = lines.iter().map(|line| line.parse::<u32>().unwrap()).collect();
+    let chain = AdapterChain::new(&adapters, jumps);
+
+    println!("--- Part 1 ---");
+    match chain.differences() {
+        Ok(differences) => {
+            for (jump, count) in &differences {
+                println!("{} jolt differences: {}", jump, count);
+            }
+            let count = |jump| differences.get(&jump).copied().unwrap_or(0);
+            println!("{} * {} = {}", count(1), count(3), count(1) * count(3));
         }
+        Err(e) => println!("Can't use every adapter: {}", e),
     }
 
-    /// Increment one of the difference values by one (accessed by indexing)
-    /// 
-    /// # Arguments
-    /// 
-    /// * `i` the difference index to increment
-    fn inc(&mut self, i: usize) {
-        match i {
-            1 => self.one += 1,
-            2 => self.two += 1,
-            3 => self.three += 1,
-            _ => (),
-        }
+    println!("\n--- Part 2 ---");
+    match chain.arrangements() {
+        Ok(count) => println!("Valid Combinations: {}", count),
+        Err(e) => println!("No valid combinations: {}", e),
     }
-}
-
 
-fn main() {
-    let lines = read_in_lines("src/adapter_ratings.txt");
-    let mut adapters = Vec::new();
-    adapters.push( 0 );  // Socket
-    for line in lines {
-        let rating = line.parse::<u32>().unwrap();
-        adapters.push( rating );        
+    let show = |arrangement: &[u32]| {
+        let ratings: Vec<String> = arrangement.iter().map(|r| r.to_string()).collect();
+        format!("{} ({} adapters)", ratings.join(" "), arrangement.len() - 2)
+    };
+    match command.as_slice() {
+        ["list", n] => {
+            for arrangement in chain.iter_arrangements().take(n.parse().unwrap()) {
+                println!("{}", show(&arrangement));
+            }
+        }
+        ["random", seed @ ..] => {
+            let seed = match seed.first() {
+                Some(s) => s.parse().unwrap(),
+                None => SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos() as u64,
+            };
+            match chain.random_arrangement(&mut XorShift::new(seed)) {
+                Ok(arrangement) => println!("Random arrangement (seed {}): {}", seed, show(&arrangement)),
+                Err(e) => println!("No arrangement: {}", e),
+            }
+        }
+        ["fewest"] => match chain.fewest_adapters() {
+            Ok(arrangement) => println!("Fewest adapters: {}", show(&arrangement)),
+            Err(e) => println!("No arrangement: {}", e),
+        },
+        _ => {}
     }
-    adapters.push( adapters.iter().max().unwrap() + 3 );  // Device
-
-    let diff = sum_rating_differences(&adapters);
-
-    println!("--- Part 1 ---\n{:#?}\n{} * {} = {}", diff, diff.one, diff.three, diff.one*diff.three);
-
-    println!("\n--- Part 2 ---\nValid Combinations: {}", valid_combinations(&adapters));
 }
 
 /// Read in lines of a file to a vector
@@ -200,81 +207,5 @@ fn read_in_lines(filename: &str) -> Vec<String> {
     let file = File::open(filename).unwrap();
     let reader = BufReader::new(file);
 
-    reader.lines().flatten().collect()
-}
-
-/// Find the differences between the valid chain of adapters.
-/// 
-/// # Arguments
-/// 
-/// * `adapters` an unsorted vector of adapters
-/// 
-/// # Returns
-/// 
-/// * A Differences struct containing the sum of 1, 2 and 3 value jumps
-fn sum_rating_differences(adapters: &Vec<Adapter>) -> Differences {
-    let mut diff = Differences::new();
-
-    let mut adapters_sorted = adapters.clone();
-    adapters_sorted.sort();
-
-    let mut prev: Option<Adapter> = None;
-    for adapter in adapters_sorted {
-        if let Some(pre) = prev {
-            diff.inc( (adapter - pre) as usize);            
-        }
-        prev = Some(adapter);
-    }
-    diff
-}
-
-/// General solution for finding valid combinations of any set of adapters.
-/// This works no matter what gaps exist between the starting set but does
-/// assume a valid solution exists.
-/// 
-/// # Arguments
-/// 
-/// * `adapters`: a vector of adapters
-/// 
-/// # Returns
-/// 
-/// * the valid number of possible combinations spanning the smallest to largest value.
-fn valid_combinations(adapters: &Vec<Adapter>) -> u64 {
-    let mut sorted = adapters.clone();
-    sorted.sort();
-    let mut map: Vec<AdapterMap> = Vec::new();
-    // Fisrt find the valid number of connections for each adapter
-    for (i, x) in sorted.iter().enumerate() {
-        //Check the next three
-        let mut possible_connections = 0;
-        for y in sorted[(i+1)..].iter() {
-            match y - x {
-                d if d < 4 => possible_connections += 1,
-                _ => break,
-            }
-        }
-        let node = AdapterMap {
-            adapter: *x,
-            possible_connections,
-        };
-        map.insert(0, node);
-    }
-
-    // Now calculate total combinations
-    let mut working_totals: Vec<u64> = Vec::new();
-    for (i, node) in map.iter().enumerate() {
-        let mut working_total = 0;
-        if i == 0 {
-            working_total = 1;
-        } else {
-            // Sum the current `working total` for the last n nodes - where n is the number
-            // of valid connections the current node can make (i.e. sum the values of the
-            // nodes this one can connect to )
-            for connection in working_totals[..node.possible_connections as usize].iter() {
-                working_total += connection;
-            }
-        }
-        working_totals.insert(0, working_total);
-    }
-    working_totals[0]
+    reader.lines().map(|l| l.unwrap()).collect()
 }
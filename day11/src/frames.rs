@@ -0,0 +1,71 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use crate::seating::{RunReport, SeatingSimulation};
+
+/// Where to send the layout after each round
+#[derive(Debug, Clone)]
+pub enum FrameOutput {
+    /// Write each round to round_NNN.txt in a directory
+    Directory(PathBuf),
+    /// Redraw each round in place in the terminal using ANSI escapes, pausing between rounds
+    Terminal(Duration),
+}
+
+impl FrameOutput {
+    /// Send directory frames to a subdirectory instead (terminal output is unchanged)
+    pub fn in_subdirectory(self, name: &str) -> Self {
+        match self {
+            FrameOutput::Directory(dir) => FrameOutput::Directory(dir.join(name)),
+            terminal => terminal,
+        }
+    }
+
+    /// Output one round
+    ///
+    /// # Arguments
+    ///
+    /// * `round` the round number (0 for the starting layout)
+    /// * `changes` the number of seats changed in the round
+    /// * `simulation` the simulation after the round
+    pub fn emit(&self, round: usize, changes: usize, simulation: &SeatingSimulation) -> io::Result<()> {
+        match self {
+            FrameOutput::Directory(dir) => {
+                fs::create_dir_all(dir)?;
+                fs::write(dir.join(format!("round_{:03}.txt", round)), simulation.render())
+            }
+            FrameOutput::Terminal(delay) => {
+                let stdout = io::stdout();
+                let mut out = stdout.lock();
+                // Clear the screen for the first frame then move the cursor home to draw over the last one
+                if round == 0 {
+                    write!(out, "\x1b[2J")?;
+                }
+                writeln!(out, "\x1b[H{}Round {} - {} changes, {} occupied\x1b[K",
+                       simulation.render(), round, changes, simulation.occupied())?;
+                out.flush()?;
+                thread::sleep(*delay);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Run a simulation until it is stable, outputting every round
+///
+/// # Arguments
+///
+/// * `simulation` the simulation to run
+/// * `max_rounds` the maximum number of rounds to simulate
+/// * `output` where to send the rounds
+///
+/// # Returns
+///
+/// * How the simulation ran, or None if seats were still changing after `max_rounds`
+pub fn run_with_frames(simulation: &mut SeatingSimulation, max_rounds: usize, output: &FrameOutput)
+    -> io::Result<Option<RunReport>> {
+    simulation.run_until_stable_with(max_rounds, |round, changes, s| output.emit(round, changes, s))
+}
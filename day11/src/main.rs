@@ -1,4 +1,4 @@
-/**
+/*
  * --- Day 11: Seating System ---
  * Your plane lands with plenty of time to spare. The final leg of your journey is a ferry that goes directly to the tropical island where you can finally start your vacation. As you reach the waiting area to board the ferry, you realize you're so early, nobody else has even arrived yet!
  * By modeling the process people use to choose (or abandon) their seat in the waiting area, you're pretty sure you can predict the best place to sit. You make a quick map of the seat layout (your puzzle input).
@@ -200,222 +200,69 @@
  * Given the new visibility method and the rule change for occupied seats becoming empty, once equilibrium is reached, how many seats end up occupied?
  */
 
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-
-fn main() {
-    part_one();
-    part_two();
-}
+mod frames;
+mod seating;
 
+use std::env;
+use std::time::Duration;
 
-/// Find the solution for part 1 - finding the stable seating layout for the given input and rules.
-fn part_one() {
-    let initial_layout = read_in_file_to_matrix("src/initial_layout.txt");
-    let rows = initial_layout.len();
-    let columns = initial_layout[0].len();
-    let mut is_changed = true;
-    let mut current = initial_layout.clone();
-    while is_changed {
-        is_changed = false;
-        let mut next: Vec< Vec<char> > = Vec::new();
-        for r in 0..rows {
-            let mut row: Vec<char> = Vec::new();
-            for c in 0..columns {
-                let occupied_adjacent = count_occupied_adjacent(r as i32, c as i32, &current);
-                match current[r][c] {
-                    'L' => {
-                        if occupied_adjacent == 0 {
-                            row.push('#');
-                            is_changed = true;
-                        } else {
-                            row.push('L');
-                        }
-                    },
-                    '#' => {
-                        if occupied_adjacent >= 4 {
-                            row.push('L');
-                            is_changed = true;
-                        } else {
-                            row.push('#');
-                        }
-                    },
-                    '.' => row.push('.'),
-                    _ => (),
-                }
-            }
-            next.push(row);
-        }
-        current = next;
-        // for row in &current {
-        //     println!("{:?}", row)
-        // }
-        // println!("\n\n");
-    }
-    println!("\n--- Part 1 ---\n");
-    println!("Total Occupied = {}", count_total_occupied(&current));   
-}
+use automaton::Neighbourhood;
+use frames::{run_with_frames, FrameOutput};
+use seating::SeatingSimulation;
 
+/// Give up on a layout which hasn't settled after this many rounds
+const MAX_ROUNDS: usize = 1000;
 
-/// Find the solution for part 2 - finding the stable seating layout for the given input using the LOS rule.
-fn part_two() {
-    let initial_layout = read_in_file_to_matrix("src/initial_layout.txt");
-    let rows = initial_layout.len();
-    let columns = initial_layout[0].len();
-    let mut is_changed = true;
-    let mut current = initial_layout.clone();
-    while is_changed {
-        is_changed = false;
-        let mut next: Vec< Vec<char> > = Vec::new();
-        for r in 0..rows {
-            let mut row: Vec<char> = Vec::new();
-            for c in 0..columns {
-                let occupied_adjacent = count_occupied_line_of_sight(r as i32, c as i32, &current);
-                match current[r][c] {
-                    'L' => {
-                        if occupied_adjacent == 0 {
-                            row.push('#');
-                            is_changed = true;
-                        } else {
-                            row.push('L');
-                        }
-                    },
-                    '#' => {
-                        if occupied_adjacent >= 5 {
-                            row.push('L');
-                            is_changed = true;
-                        } else {
-                            row.push('#');
-                        }
-                    },
-                    '.' => row.push('.'),
-                    _ => (),
-                }
-            }
-            next.push(row);
+fn main() {
+    // Optional arguments:
+    //   --frames <dir>      write each round to <dir>/part1 and <dir>/part2 as round_NNN.txt
+    //   --animate [delay]   redraw each round in the terminal, pausing for delay ms (default 100)
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+    let output = match args.as_slice() {
+        [] => None,
+        ["--frames", dir] => Some(FrameOutput::Directory(dir.into())),
+        ["--animate"] => Some(FrameOutput::Terminal(Duration::from_millis(100))),
+        ["--animate", delay] if delay.parse::<u64>().is_ok() =>
+            Some(FrameOutput::Terminal(Duration::from_millis(delay.parse().unwrap()))),
+        _ => {
+            println!("Usage: day11 [--frames <dir> | --animate [delay ms]]");
+            return;
         }
-        current = next;
-    }
-    println!("\n--- Part 2 ---\n");
-    // for row in &current {
-    //     println!("{:?}", row)
-    // }
-    println!("Total Occupied = {}", count_total_occupied(&current));   
-}
-
-
-/// Read in lines of a file to a vector
-/// 
-/// # Arguments
-/// 
-/// * `filename` - String containing the filename
-fn read_in_file_to_matrix(filename: &str) -> Vec<Vec<char>> {
-    // Open the file in read-only mode (ignoring errors).
-    let file = File::open(filename).unwrap();
-    let reader = BufReader::new(file);
+    };
+    let output = |part: &str| output.clone().map(|o| o.in_subdirectory(part));
 
-    reader.lines().map(|x| -> Vec<char> { x.unwrap().chars().collect() } ).collect()
+    // Part 1 - finding the stable seating layout for the given input and rules
+    solve("Part 1", Neighbourhood::Adjacent, 4, output("part1"));
+    // Part 2 - finding the stable seating layout for the given input using the LOS rule
+    solve("Part 2", Neighbourhood::LineOfSight, 5, output("part2"));
 }
 
 
-/// Counts all occupied seats (#) directly adjacent to the given seat
-/// 
+/// Run the seating simulation until it is stable and report how it got there
+///
 /// # Arguments
-/// 
-/// * `r` the row of the seat to check for
-/// * `c` the column of the seat to check for
-/// * `seating` state of the seating (charachter vector: # = occupied, L = empty, . = floor)
-/// 
-/// # Returns
-/// 
-/// * The number of occupied seats (#) adjacent to the one given
-fn count_occupied_adjacent(r: i32, c: i32, seating: &Vec< Vec<char> >) -> u32 {
-    let rows = seating.len() as i32;
-    let cols = seating[0].len() as i32;
-    
-    let mut count = 0;
-    for i in r-1..=r+1 {
-        for j in c-1..=c+1 {
-            if i == r && j == c {
-                continue
-            }
-            match (i, j) {
-                (i, j) if i >= 0 && i < rows && j >= 0 && j < cols => {
-                    match seating[i as usize][j as usize] {
-                        '#' => count += 1,
-                        _ => (),
-                    }
-                }
-                _ => (),
-            }
+///
+/// * `title` the heading to print
+/// * `neighbourhood` which seats each passenger considers
+/// * `threshold` how many occupied neighbours make a passenger leave
+/// * `output` where to send each round (if anywhere)
+fn solve(title: &str, neighbourhood: Neighbourhood, threshold: usize, output: Option<FrameOutput>) {
+    let mut simulation = SeatingSimulation::load("src/initial_layout.txt", neighbourhood, threshold).unwrap();
+    let report = match &output {
+        Some(output) => run_with_frames(&mut simulation, MAX_ROUNDS, output).unwrap(),
+        None => simulation.run_until_stable(MAX_ROUNDS),
+    };
+    println!("\n--- {} ---\n", title);
+    let report = match report {
+        Some(report) => report,
+        None => {
+            println!("Still changing after {} rounds", MAX_ROUNDS);
+            return;
         }
-    }
-    count
+    };
+    println!("Stable after {} rounds", report.rounds);
+    let changes: Vec<String> = report.changes.iter().map(|c| c.to_string()).collect();
+    println!("Changes per round: {}", changes.join(", "));
+    println!("Total Occupied = {}", report.occupied);
 }
-
-/// Counts all occupied seats (#) directly in line of sight of the given seat
-/// 
-/// # Arguments
-/// 
-/// * `r` the row of the seat to check for
-/// * `c` the column of the seat to check for
-/// * `seating` state of the seating (charachter vector: # = occupied, L = empty, . = floor)
-/// 
-/// # Returns
-/// 
-/// * The number of occupied seats (#) adjacent to the one given
-fn count_occupied_line_of_sight(r: i32, c: i32, seating: &Vec< Vec<char> >) -> u32 {
-    let rows = seating.len() as i32;
-    let cols = seating[0].len() as i32;
-
-    fn seat_exists(r: i32, c: i32, max_rows: i32, max_cols: i32) -> bool {
-        r >= 0 && r < max_rows && c >= 0 && c < max_cols
-    }
-    
-    let mut count = 0;
-    for dir_r in &[1, 0, -1] {
-        for dir_c in &[1, 0, -1] {
-            if *dir_r == 0 && *dir_c == 0 {
-                continue;
-            }
-            let mut x = r + dir_r;
-            let mut y = c + dir_c;
-            while seat_exists(x, y, rows, cols) {
-                match seating[x as usize][y as usize] {
-                    '#' => {
-                        count += 1;
-                        break;
-                    },
-                    'L' => break,
-                    _ => (),
-                }
-                x += dir_r;
-                y += dir_c;
-            }
-        }
-    }
-    count
-}
-
-
-/// Count the total number of occupied seats
-/// 
-/// # Arguments
-/// 
-/// * `seating` the current seating layout (charachter vector: # = occupied, L = empty, . = floor)
-/// 
-/// # Returns
-/// 
-/// * the number of occupied seats (represented by #)
-fn count_total_occupied(seating: &Vec< Vec<char> >) -> u64 {
-    let mut count = 0;
-    for row in seating {
-        for seat in row {
-            match seat {
-                '#' => count += 1,
-                _ => (),
-            }
-        }
-    }
-    count
-}
\ No newline at end of file
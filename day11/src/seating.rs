@@ -0,0 +1,152 @@
+use std::convert::Infallible;
+use std::fs;
+
+use automaton::{CellularAutomaton, Neighbourhood, Rule, SquareGrid, Topology};
+
+/// How a simulation ran until it was stable
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunReport {
+    /// Number of rounds which changed a seat
+    pub rounds: usize,
+    /// Number of seats changed in each of those rounds
+    pub changes: Vec<usize>,
+    /// Number of occupied seats once stable
+    pub occupied: usize,
+}
+
+/// The seating layout simulation.
+///
+/// Seats are the cells of a square grid automaton (floor isn't a cell) using the dense backend, so each
+/// seat's neighbours are worked out once up front and a round is just a count over the neighbour table.
+pub struct SeatingSimulation {
+    rows: usize,
+    columns: usize,
+    seats: CellularAutomaton<SquareGrid>,
+}
+
+impl SeatingSimulation {
+    /// Create a simulation from a layout (L = empty seat, # = occupied seat, . = floor)
+    ///
+    /// # Arguments
+    ///
+    /// * `layout` the layout with a line per row
+    /// * `neighbourhood` which seats each seat considers
+    /// * `threshold` how many occupied neighbours make a passenger leave (4 for adjacent, 5 for line of sight)
+    pub fn parse(layout: &str, neighbourhood: Neighbourhood, threshold: usize) -> Result<Self, String> {
+        if threshold == 0 {
+            return Err("threshold must be at least 1".to_string());
+        }
+        let grid: Vec<Vec<char>> = layout.lines()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty())
+            .map(|l| l.chars().collect())
+            .collect();
+        let rows = grid.len();
+        let columns = grid.first().map(|r| r.len()).unwrap_or(0);
+
+        let mut mask = vec![vec![false; columns]; rows];
+        let mut occupied = Vec::new();
+        for (r, row) in grid.iter().enumerate() {
+            if row.len() != columns {
+                return Err(format!("row {} has {} places, expected {}", r + 1, row.len(), columns));
+            }
+            for (c, place) in row.iter().enumerate() {
+                match place {
+                    'L' => mask[r][c] = true,
+                    '#' => {
+                        mask[r][c] = true;
+                        occupied.push((r as i32, c as i32));
+                    }
+                    '.' => {}
+                    p => return Err(format!("unknown place '{}' at row {} column {}", p, r + 1, c + 1)),
+                }
+            }
+        }
+
+        // Empty seats with no occupied neighbours are filled and occupied seats with at least
+        // `threshold` occupied neighbours are vacated
+        let rule = Rule::from_fn(move |occupied, count| if occupied { count < threshold } else { count == 0 });
+        let seats = CellularAutomaton::dense(SquareGrid::new(mask, neighbourhood), rule, occupied);
+        Ok(SeatingSimulation { rows, columns, seats })
+    }
+
+    /// Load a layout from a file (see `parse`)
+    pub fn load(filename: &str, neighbourhood: Neighbourhood, threshold: usize) -> Result<Self, String> {
+        let layout = fs::read_to_string(filename).map_err(|e| format!("{}: {}", filename, e))?;
+        SeatingSimulation::parse(&layout, neighbourhood, threshold)
+    }
+
+    /// Run one round - empty seats with no occupied neighbours are filled and occupied seats with
+    /// at least `threshold` occupied neighbours are vacated
+    ///
+    /// # Returns
+    ///
+    /// * The number of seats which changed
+    pub fn step(&mut self) -> usize {
+        self.seats.step()
+    }
+
+    /// Run rounds until no seats change
+    ///
+    /// # Arguments
+    ///
+    /// * `max_rounds` the maximum number of rounds to simulate
+    ///
+    /// # Returns
+    ///
+    /// * How the simulation ran, or None if seats were still changing after `max_rounds`
+    pub fn run_until_stable(&mut self, max_rounds: usize) -> Option<RunReport> {
+        match self.run_until_stable_with(max_rounds, |_, _, _| Ok::<(), Infallible>(())) {
+            Ok(report) => report,
+            Err(never) => match never {},
+        }
+    }
+
+    /// Run rounds until no seats change, calling `on_round` with the starting layout (round 0)
+    /// and after each round which changed a seat
+    ///
+    /// # Arguments
+    ///
+    /// * `max_rounds` the maximum number of rounds to simulate
+    /// * `on_round` called with the round number, the seats changed in that round and the simulation -
+    ///   an error stops the run
+    ///
+    /// # Returns
+    ///
+    /// * How the simulation ran, or None if seats were still changing after `max_rounds`
+    pub fn run_until_stable_with<E, F>(&mut self, max_rounds: usize, mut on_round: F) -> Result<Option<RunReport>, E>
+    where
+        F: FnMut(usize, usize, &Self) -> Result<(), E>,
+    {
+        on_round(0, 0, self)?;
+        let mut changes = Vec::new();
+        for _ in 0..max_rounds {
+            let changed = self.step();
+            if changed == 0 {
+                return Ok(Some(RunReport { rounds: changes.len(), changes, occupied: self.occupied() }));
+            }
+            changes.push(changed);
+            on_round(changes.len(), changed, self)?;
+        }
+        Ok(None)
+    }
+
+    /// Draw the layout with a line per row (L = empty seat, # = occupied seat, . = floor)
+    pub fn render(&self) -> String {
+        let mut grid = vec![vec!['.'; self.columns]; self.rows];
+        for (r, c) in self.seats.topology().cells().unwrap() {
+            grid[r as usize][c as usize] = if self.seats.is_alive((r, c)) { '#' } else { 'L' };
+        }
+        let mut out = String::with_capacity(self.rows * (self.columns + 1));
+        for row in grid {
+            out.extend(row);
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Number of occupied seats
+    pub fn occupied(&self) -> usize {
+        self.seats.live_count()
+    }
+}
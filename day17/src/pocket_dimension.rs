@@ -0,0 +1,89 @@
+use automaton::{CellularAutomaton, Lattice, Rule};
+
+/// Coordinate of a cube in a D-dimensional pocket dimension
+pub type Coordinate<const D: usize> = [i32; D];
+
+/// Sparse pocket dimension of Conway cubes - a D-dimensional lattice automaton storing only the active cubes
+pub struct PocketDimension<const D: usize> {
+    cubes: CellularAutomaton<Lattice<D>>,
+}
+
+impl<const D: usize> PocketDimension<D> {
+    /// Create a pocket dimension from a 2D slice (all other coordinates are 0)
+    ///
+    /// The rules for each cycle are:
+    /// * If a cube is active and exactly 2 or 3 of its neighbors are also active, the cube remains active. Otherwise, the cube becomes inactive.
+    /// * If a cube is inactive but exactly 3 of its neighbors are active, the cube becomes active. Otherwise, the cube remains inactive.
+    ///
+    /// # Arguments
+    ///
+    /// * `plane` rows of the slice (# = active, . = inactive)
+    pub fn from_plane(plane: &[Vec<char>]) -> Self {
+        assert!(D >= 2, "A pocket dimension needs at least 2 dimensions to hold a plane");
+        let mut active = Vec::new();
+        for (y, row) in plane.iter().enumerate() {
+            for (x, cube) in row.iter().enumerate() {
+                match cube {
+                    '#' => {
+                        let mut p = [0; D];
+                        p[0] = x as i32;
+                        p[1] = y as i32;
+                        active.push(p);
+                    }
+                    '.' => (),
+                    c => panic!("Unrecognised character: {}", c),
+                }
+            }
+        }
+        let cubes = CellularAutomaton::sparse(Lattice, Rule::birth_survival(&[3], &[2, 3]), active);
+        PocketDimension { cubes }
+    }
+
+    /// Count the number of active cubes
+    pub fn count_active(&self) -> usize {
+        self.cubes.live_count()
+    }
+
+    /// Run a single cycle of the rules
+    pub fn cycle(&mut self) {
+        self.cubes.step();
+    }
+}
+
+/// Boot up a D-dimensional pocket dimension from a 2D slice and count the active cubes afterwards
+///
+/// # Arguments
+///
+/// * `plane` the initial 2D slice
+/// * `cycles` the number of cycles to run
+pub fn simulate<const D: usize>(plane: &[Vec<char>], cycles: usize) -> usize {
+    let mut dimension = PocketDimension::<D>::from_plane(plane);
+    for _ in 0..cycles {
+        dimension.cycle();
+    }
+    dimension.count_active()
+}
+
+/// Run `simulate` for a dimension count only known at runtime
+///
+/// # Arguments
+///
+/// * `dimensions` the number of dimensions (2 to 8)
+/// * `plane` the initial 2D slice
+/// * `cycles` the number of cycles to run
+///
+/// # Returns
+///
+/// * The number of active cubes or None if the dimension count isn't supported
+pub fn simulate_dimensions(dimensions: usize, plane: &[Vec<char>], cycles: usize) -> Option<usize> {
+    match dimensions {
+        2 => Some(simulate::<2>(plane, cycles)),
+        3 => Some(simulate::<3>(plane, cycles)),
+        4 => Some(simulate::<4>(plane, cycles)),
+        5 => Some(simulate::<5>(plane, cycles)),
+        6 => Some(simulate::<6>(plane, cycles)),
+        7 => Some(simulate::<7>(plane, cycles)),
+        8 => Some(simulate::<8>(plane, cycles)),
+        _ => None,
+    }
+}
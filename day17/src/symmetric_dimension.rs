@@ -0,0 +1,197 @@
+use std::collections::{HashMap, HashSet};
+
+use automaton::Lattice;
+
+use crate::pocket_dimension::Coordinate;
+
+/// Pocket dimension which exploits the symmetry of the extra axes.
+///
+/// The initial state is a 2D slice (x, y) with every other axis 0, so the state is always symmetric under
+/// reflection of each extra axis (w -> -w) and under any permutation of the extra axes. Each stored
+/// coordinate is the canonical member of its symmetry class: the extra axes are made positive and sorted.
+/// Neighbour counts are weighted by how many real neighbours fall in each class.
+#[derive(Debug, Clone)]
+pub struct SymmetricPocketDimension<const D: usize> {
+    active: HashSet<Coordinate<D>>,
+    /// For each canonical extra-axes vector u: the canonical vectors v reached by stepping u by {-1, 0, 1}
+    /// along each extra axis, with the number of steps landing in each v
+    weights: HashMap<Coordinate<D>, Vec<(Coordinate<D>, usize)>>,
+}
+
+impl<const D: usize> SymmetricPocketDimension<D> {
+    /// Create a symmetric pocket dimension from a 2D slice (all other coordinates are 0)
+    ///
+    /// # Arguments
+    ///
+    /// * `plane` rows of the slice (# = active, . = inactive)
+    pub fn from_plane(plane: &[Vec<char>]) -> Self {
+        assert!(D >= 2, "A pocket dimension needs at least 2 dimensions to hold a plane");
+        let mut active = HashSet::new();
+        for (y, row) in plane.iter().enumerate() {
+            for (x, cube) in row.iter().enumerate() {
+                match cube {
+                    '#' => {
+                        let mut p = [0; D];
+                        p[0] = x as i32;
+                        p[1] = y as i32;
+                        active.insert(p);
+                    }
+                    '.' => (),
+                    c => panic!("Unrecognised character: {}", c),
+                }
+            }
+        }
+        SymmetricPocketDimension { active, weights: HashMap::new() }
+    }
+
+    /// Map a coordinate to the canonical member of its symmetry class (extra axes positive and sorted)
+    pub fn canonical(mut point: Coordinate<D>) -> Coordinate<D> {
+        for x in point[2..].iter_mut() {
+            *x = x.abs();
+        }
+        point[2..].sort_unstable();
+        point
+    }
+
+    /// Number of real coordinates represented by a canonical coordinate
+    ///
+    /// # Arguments
+    ///
+    /// * `point` a canonical coordinate
+    pub fn multiplicity(point: &Coordinate<D>) -> u64 {
+        let extra = &point[2..];
+        // Distinct orderings of the extra axes - k! / (n1! n2! ...) for runs of equal values
+        let mut permutations = 1u64;
+        let mut run = 0;
+        for (i, x) in extra.iter().enumerate() {
+            run = if i > 0 && extra[i - 1] == *x { run + 1 } else { 1 };
+            permutations = permutations * (i as u64 + 1) / run;
+        }
+        // Each non-zero axis can also be reflected
+        let reflections = 1u64 << extra.iter().filter(|x| **x != 0).count();
+        permutations * reflections
+    }
+
+    /// Find (and cache) the weighted canonical neighbours of the extra axes of a canonical coordinate
+    ///
+    /// # Arguments
+    ///
+    /// * `extra` canonical coordinate with the x and y axes set to 0
+    fn extra_weights(&mut self, extra: Coordinate<D>) -> &Vec<(Coordinate<D>, usize)> {
+        self.weights.entry(extra).or_insert_with(|| {
+            let mut tally: HashMap<Coordinate<D>, usize> = HashMap::new();
+            for p in Lattice::<D>::around(extra, 2..D) {
+                *tally.entry(SymmetricPocketDimension::<D>::canonical(p)).or_insert(0) += 1;
+            }
+            tally.into_iter().collect()
+        })
+    }
+
+    /// Split a coordinate into its (x, y) plane position and its extra axes (with x and y set to 0)
+    fn split(point: Coordinate<D>) -> ((i32, i32), Coordinate<D>) {
+        let mut extra = point;
+        extra[0] = 0;
+        extra[1] = 0;
+        ((point[0], point[1]), extra)
+    }
+
+    /// Join an (x, y) plane position with the extra axes
+    fn join(plane: (i32, i32), mut extra: Coordinate<D>) -> Coordinate<D> {
+        extra[0] = plane.0;
+        extra[1] = plane.1;
+        extra
+    }
+
+    /// Count the active cubes around a canonical coordinate (including all mirrored neighbours)
+    fn count_around(&mut self, point: Coordinate<D>) -> usize {
+        let ((x, y), extra) = SymmetricPocketDimension::<D>::split(point);
+        self.extra_weights(extra);
+        let weights = &self.weights[&extra];
+        let mut count = 0;
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                for (v, w) in weights {
+                    if self.active.contains(&SymmetricPocketDimension::<D>::join((x + dx, y + dy), *v)) {
+                        count += w;
+                    }
+                }
+            }
+        }
+        // The point itself was included (dx = dy = 0 with no step on the extra axes)
+        if self.active.contains(&point) {
+            count -= 1;
+        }
+        count
+    }
+
+    /// Run a single cycle of the Conway cube rules
+    pub fn cycle(&mut self) {
+        // Candidates are the canonical coordinates next to any active cube
+        let mut candidates = HashSet::new();
+        let active: Vec<Coordinate<D>> = self.active.iter().copied().collect();
+        for point in active {
+            let ((x, y), extra) = SymmetricPocketDimension::<D>::split(point);
+            let neighbours: Vec<Coordinate<D>> = self.extra_weights(extra).iter().map(|(v, _)| *v).collect();
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    for v in &neighbours {
+                        candidates.insert(SymmetricPocketDimension::<D>::join((x + dx, y + dy), *v));
+                    }
+                }
+            }
+        }
+        let mut next = HashSet::new();
+        for point in candidates {
+            let count = self.count_around(point);
+            if count == 3 || (count == 2 && self.active.contains(&point)) {
+                next.insert(point);
+            }
+        }
+        self.active = next;
+    }
+
+    /// Count the number of active cubes in the full (unreduced) pocket dimension
+    pub fn count_active(&self) -> u64 {
+        self.active.iter().map(SymmetricPocketDimension::<D>::multiplicity).sum()
+    }
+}
+
+/// Boot up a D-dimensional pocket dimension using the symmetry-reduced simulation
+///
+/// # Arguments
+///
+/// * `plane` the initial 2D slice
+/// * `cycles` the number of cycles to run
+pub fn simulate_symmetric<const D: usize>(plane: &[Vec<char>], cycles: usize) -> u64 {
+    let mut dimension = SymmetricPocketDimension::<D>::from_plane(plane);
+    for _ in 0..cycles {
+        dimension.cycle();
+    }
+    dimension.count_active()
+}
+
+/// Run `simulate_symmetric` for a dimension count only known at runtime
+///
+/// # Arguments
+///
+/// * `dimensions` the number of dimensions (2 to 10)
+/// * `plane` the initial 2D slice
+/// * `cycles` the number of cycles to run
+///
+/// # Returns
+///
+/// * The number of active cubes or None if the dimension count isn't supported
+pub fn simulate_symmetric_dimensions(dimensions: usize, plane: &[Vec<char>], cycles: usize) -> Option<u64> {
+    match dimensions {
+        2 => Some(simulate_symmetric::<2>(plane, cycles)),
+        3 => Some(simulate_symmetric::<3>(plane, cycles)),
+        4 => Some(simulate_symmetric::<4>(plane, cycles)),
+        5 => Some(simulate_symmetric::<5>(plane, cycles)),
+        6 => Some(simulate_symmetric::<6>(plane, cycles)),
+        7 => Some(simulate_symmetric::<7>(plane, cycles)),
+        8 => Some(simulate_symmetric::<8>(plane, cycles)),
+        9 => Some(simulate_symmetric::<9>(plane, cycles)),
+        10 => Some(simulate_symmetric::<10>(plane, cycles)),
+        _ => None,
+    }
+}
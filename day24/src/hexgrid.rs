@@ -1,12 +1,336 @@
 use std::collections::HashMap;
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use automaton::Topology;
 
 /// HashMap for storing HexTiles with their corresponding coordinate
-pub type HexGrid = HashMap<HexCoordinate, HexTile>;
+pub type HexGrid = HashMap<Hex, HexTile>;
 
 /// Axial coordinate in a HexGrid
-pub type HexCoordinate = (i32, i32);
+pub type HexCoordinate = Hex;
+
+/// Coordinate of a pointy-top hexagon, stored as axial coordinates
+///
+/// `r` is the north-east diagonal "row" (increasing towards the north) and `c` is the east "column"
+/// (increasing towards the east), i.e. from (0, 0)
+///    ne -> (1, 0)
+///    e  -> (0, 1)
+///    se -> (-1, 1)
+///    sw -> (-1, 0)
+///    w  -> (0, -1)
+///    nw -> (1, -1)
+///
+/// Conversions are provided to cube coordinates (x, y, z) where x + y + z = 0 and
+/// to "odd-r" offset coordinates (col, row) where rows run from north to south.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Hex {
+    pub r: i32,
+    pub c: i32,
+}
+
+impl Hex {
+    /// Create a new hex from axial coordinates
+    ///
+    /// # Arguments
+    ///
+    /// * `r` the north-east diagonal row
+    /// * `c` the east column
+    pub fn new(r: i32, c: i32) -> Self {
+        Hex { r, c }
+    }
+
+    /// Create a hex from cube coordinates
+    /// x increases to the east/north-east, y to the west/north-west and z to the south-east/south-west
+    ///
+    /// # Arguments
+    ///
+    /// * `cube` (x, y, z) cube coordinate - must satisfy x + y + z = 0
+    pub fn from_cube(cube: (i32, i32, i32)) -> Self {
+        debug_assert_eq!(cube.0 + cube.1 + cube.2, 0, "Invalid cube coordinate {:?}", cube);
+        Hex { r: -cube.2, c: -cube.1 }
+    }
+
+    /// Convert to cube coordinates (x, y, z) with x + y + z = 0
+    pub fn to_cube(self) -> (i32, i32, i32) {
+        (self.c + self.r, -self.c, -self.r)
+    }
+
+    /// Create a hex from "odd-r" offset coordinates (odd rows are shifted half a tile east)
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` the (col, row) coordinate - rows increase towards the south
+    pub fn from_offset(offset: (i32, i32)) -> Self {
+        let (col, row) = offset;
+        let x = col - (row - (row & 1)) / 2;
+        Hex::from_cube((x, -x - row, row))
+    }
+
+    /// Convert to "odd-r" offset coordinates (col, row) - rows increase towards the south
+    pub fn to_offset(self) -> (i32, i32) {
+        let (x, _, z) = self.to_cube();
+        (x + (z - (z & 1)) / 2, z)
+    }
+
+    /// Return the neighbouring hex in the given direction
+    pub fn neighbour(self, direction: Direction) -> Self {
+        self + direction.offset()
+    }
+
+    /// Iterate over the six neighbours of this hex (starting east and moving clockwise)
+    pub fn neighbours(self) -> impl Iterator<Item = Hex> {
+        Direction::ALL.iter().map(move |d| self.neighbour(*d))
+    }
+
+    /// Number of steps between two hexes
+    pub fn distance(self, other: Hex) -> i32 {
+        let (x, y, z) = (self - other).to_cube();
+        x.abs().max(y.abs()).max(z.abs())
+    }
+
+    /// All hexes exactly `radius` steps from this one (clockwise, starting from the south-west corner)
+    ///
+    /// # Arguments
+    ///
+    /// * `radius` the distance of the ring from this hex (a radius of 0 returns only this hex)
+    pub fn ring(self, radius: u32) -> Vec<Hex> {
+        if radius == 0 {
+            return vec!(self);
+        }
+        let mut results = Vec::with_capacity(6 * radius as usize);
+        let mut h = self + Direction::SouthWest.offset() * radius as i32;
+        for d in &[Direction::NorthWest, Direction::NorthEast, Direction::East,
+                   Direction::SouthEast, Direction::SouthWest, Direction::West] {
+            for _ in 0..radius {
+                results.push(h);
+                h = h.neighbour(*d);
+            }
+        }
+        results
+    }
+
+    /// All hexes within `radius` steps of this one, working outwards ring by ring
+    ///
+    /// # Arguments
+    ///
+    /// * `radius` the maximum distance from this hex
+    pub fn spiral(self, radius: u32) -> Vec<Hex> {
+        (0..=radius).flat_map(|k| self.ring(k)).collect()
+    }
+
+    /// Hexes on the straight line between this hex and another (inclusive of both ends)
+    ///
+    /// # Arguments
+    ///
+    /// * `other` the end of the line
+    pub fn line_to(self, other: Hex) -> Vec<Hex> {
+        let n = self.distance(other);
+        if n == 0 {
+            return vec!(self);
+        }
+        // Nudge the start point slightly so points on an edge consistently round the same way
+        let a = self.to_cube();
+        let b = other.to_cube();
+        let a = (a.0 as f64 + 1e-6, a.1 as f64 + 1e-6, a.2 as f64 - 2e-6);
+        let b = (b.0 as f64 + 1e-6, b.1 as f64 + 1e-6, b.2 as f64 - 2e-6);
+        (0..=n).map(|i| {
+            let t = i as f64 / n as f64;
+            Hex::round_cube((a.0 + (b.0 - a.0) * t,
+                             a.1 + (b.1 - a.1) * t,
+                             a.2 + (b.2 - a.2) * t))
+        }).collect()
+    }
+
+    /// Round a fractional cube coordinate to the nearest hex
+    fn round_cube(cube: (f64, f64, f64)) -> Hex {
+        let (mut x, mut y, mut z) = (cube.0.round(), cube.1.round(), cube.2.round());
+        let dx = (x - cube.0).abs();
+        let dy = (y - cube.1).abs();
+        let dz = (z - cube.2).abs();
+        // Recalculate the component with the largest rounding error so that x + y + z = 0
+        if dx > dy && dx > dz {
+            x = -y - z;
+        } else if dy > dz {
+            y = -x - z;
+        } else {
+            z = -x - y;
+        }
+        Hex::from_cube((x as i32, y as i32, z as i32))
+    }
+
+    /// Rotate this hex about the origin in steps of 60 degrees
+    ///
+    /// # Arguments
+    ///
+    /// * `steps` the number of 60 degree steps (positive is clockwise, negative anti-clockwise)
+    pub fn rotate(self, steps: i32) -> Self {
+        let (mut x, mut y, mut z) = self.to_cube();
+        for _ in 0..steps.rem_euclid(6) {
+            let t = (-z, -x, -y);
+            x = t.0;
+            y = t.1;
+            z = t.2;
+        }
+        Hex::from_cube((x, y, z))
+    }
 
-#[derive(Clone, Debug)]
+    /// Rotate this hex about another hex in steps of 60 degrees
+    ///
+    /// # Arguments
+    ///
+    /// * `centre` the hex to rotate around
+    /// * `steps` the number of 60 degree steps (positive is clockwise, negative anti-clockwise)
+    pub fn rotate_around(self, centre: Hex, steps: i32) -> Self {
+        (self - centre).rotate(steps) + centre
+    }
+}
+
+impl Add for Hex {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Hex::new(self.r + other.r, self.c + other.c)
+    }
+}
+
+impl Sub for Hex {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Hex::new(self.r - other.r, self.c - other.c)
+    }
+}
+
+impl Neg for Hex {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Hex::new(-self.r, -self.c)
+    }
+}
+
+impl Mul<i32> for Hex {
+    type Output = Self;
+
+    fn mul(self, k: i32) -> Self {
+        Hex::new(self.r * k, self.c * k)
+    }
+}
+
+impl fmt::Display for Hex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.r, self.c)
+    }
+}
+
+/// The six directions between neighbouring pointy-top hexagons
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Direction {
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthEast,
+}
+
+impl Direction {
+    /// All directions, clockwise starting from east
+    pub const ALL: [Direction; 6] = [
+        Direction::East,
+        Direction::SouthEast,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+        Direction::NorthEast,
+    ];
+
+    /// The change in axial coordinate from moving one step in this direction
+    pub fn offset(self) -> Hex {
+        match self {
+            Direction::East => Hex::new(0, 1),
+            Direction::SouthEast => Hex::new(-1, 1),
+            Direction::SouthWest => Hex::new(-1, 0),
+            Direction::West => Hex::new(0, -1),
+            Direction::NorthWest => Hex::new(1, -1),
+            Direction::NorthEast => Hex::new(1, 0),
+        }
+    }
+
+    /// The direction token used in the puzzle input (e, se, sw, w, nw or ne)
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Direction::East => "e",
+            Direction::SouthEast => "se",
+            Direction::SouthWest => "sw",
+            Direction::West => "w",
+            Direction::NorthWest => "nw",
+            Direction::NorthEast => "ne",
+        }
+    }
+}
+
+/// Error returned when a direction string contains an unrecognised token
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectionError {
+    /// Character index of the start of the bad token
+    pub position: usize,
+    /// The token that could not be interpreted
+    pub token: String,
+}
+
+impl fmt::Display for DirectionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unrecognised direction \"{}\" at position {}", self.token, self.position)
+    }
+}
+
+impl std::error::Error for DirectionError {}
+
+/// Split a string of directions with no delimiter (e.g. "nwwswee") into its directions.
+/// Whitespace between directions is ignored.
+///
+/// # Arguments
+///
+/// * `directions` cardinal directions (e, se, sw, w, nw, ne) with no delimiter
+///
+/// # Returns
+///
+/// * The list of directions or an error identifying the first bad token
+pub fn parse_directions(directions: &str) -> Result<Vec<Direction>, DirectionError> {
+    let mut result = Vec::new();
+    let mut chars = directions.char_indices().peekable();
+    while let Some((i, ch)) = chars.next() {
+        let direction = match ch {
+            'e' => Direction::East,
+            'w' => Direction::West,
+            'n' | 's' => {
+                let second = chars.next_if(|(_, c)| *c == 'e' || *c == 'w').map(|(_, c)| c);
+                match (ch, second) {
+                    ('n', Some('e')) => Direction::NorthEast,
+                    ('n', Some('w')) => Direction::NorthWest,
+                    ('s', Some('e')) => Direction::SouthEast,
+                    ('s', Some('w')) => Direction::SouthWest,
+                    _ => {
+                        // Report the half-direction along with whatever followed it
+                        let mut token = ch.to_string();
+                        if let Some((_, c)) = chars.peek() {
+                            token.push(*c);
+                        }
+                        return Err(DirectionError { position: i, token });
+                    }
+                }
+            }
+            c if c.is_whitespace() => continue,
+            c => return Err(DirectionError { position: i, token: c.to_string() }),
+        };
+        result.push(direction);
+    }
+    Ok(result)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 /// Black or White colour
 pub enum Colours {
     White,
@@ -52,38 +376,28 @@ impl HexTile {
     ///    sw -> (-1, +0)
     ///    w  -> (+0, -1)
     ///    nw -> (+1 ,-1)
-    /// 
+    ///
     /// # Arguments
-    /// 
+    ///
     /// * `directions` cardinal directions with no delimiter
-    /// 
+    ///
     /// # Returns
-    /// 
+    ///
     /// * The hex coordinate - using an axial coordinate system
-    pub fn find_position(directions: &String) -> HexCoordinate {
-        let mut r = 0;
-        let mut c = 0;       
-        let mut temp: Option<char> = None;
-        for ch in directions.chars() {
-            if let Some(t) = temp {
-                match (t, ch) {
-                    ('n', 'e') => { r += 1; }
-                    ('n', 'w') => { r += 1; c -= 1; }
-                    ('s', 'e') => { r -= 1; c += 1; }
-                    ('s', 'w') => { r -= 1; }
-                    _ => panic!("Unrecognised direction {}{}", t, c),
-                }
-                temp = None;
-            } else {
-                match ch {
-                    'n' => temp = Some('n'),
-                    'e' => c += 1,
-                    's' => temp = Some('s'),
-                    'w' => c -= 1,
-                    _ => panic!("Unrecognised direction {}", c),
-                }
-            }
-        }
-        (r, c)
+    pub fn find_position(directions: &str) -> Result<HexCoordinate, DirectionError> {
+        let steps = parse_directions(directions)?;
+        Ok(steps.iter().fold(Hex::default(), |p, d| p.neighbour(*d)))
+    }
+}
+
+/// Infinite grid of pointy-top hexagons where each tile has six neighbours
+#[derive(Debug, Copy, Clone, Default)]
+pub struct HexTopology;
+
+impl Topology for HexTopology {
+    type Cell = Hex;
+
+    fn neighbours(&self, cell: Hex) -> Vec<Hex> {
+        cell.neighbours().collect()
     }
 }
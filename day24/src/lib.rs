@@ -0,0 +1,5 @@
+pub mod hexgrid;
+pub use hexgrid::*;
+
+pub mod render;
+pub use render::*;
@@ -1,17 +1,28 @@
-use std::fs::File;
+use std::env;
+use std::fs::{self, File};
+use std::path::Path;
 use std::io::{BufRead, BufReader};
 
 use std::collections::HashMap;
 
-mod hexgrid;
-use hexgrid::{HexTile, Colours, HexGrid, HexCoordinate};
+use automaton::{CellularAutomaton, Rule};
+use lobby::{HexTile, Colours, HexGrid, HexTopology, black_tile_grid, render_svg, render_ascii};
 
 fn main() {
+    // Optionally write an SVG and ASCII frame for each day of the exhibit with `--frames <directory>`
+    let frames_dir = env::args().skip_while(|a| a != "--frames").nth(1);
+
     // Read in the tiles and interpret the directions for placing/flipping tiles
     let lines = read_in_lines("src/positions.txt");
     let mut tiles: HexGrid = HashMap::new();
-    for line in &lines {
-        let position = HexTile::find_position(&line);
+    for (i, line) in lines.iter().enumerate() {
+        let position = match HexTile::find_position(line) {
+            Ok(p) => p,
+            Err(e) => {
+                println!("Skipping line {}: {}", i + 1, e);
+                continue;
+            }
+        };
         if let Some(tile) = tiles.get_mut(&position) {
             // If tile already exists then flip it
             tile.flip();
@@ -23,19 +34,6 @@ fn main() {
         }
     }
     
-    // Create grid with only black tiles and their neighbours (since that is all we care about on each iteration)
-    let mut next: HexGrid = HashMap::new();
-    for tile in tiles.values() {
-        match tile.colour {
-            Colours::Black => {
-                next.insert(tile.position, tile.clone());
-                insert_around_tile(tile.position, &mut next);
-            }
-            _ => (),
-        }
-    }
-    tiles = next;
-
     println!("\n--- Part 1 ---");
     println!("Number of black tiles: {}", count_black_tiles(&tiles));
 
@@ -43,33 +41,34 @@ fn main() {
     // Iterate 100 times according to the rules:
     // *  Any black tile with zero or more than 2 black tiles immediately adjacent to it is flipped to white.
     // *  Any white tile with exactly 2 black tiles immediately adjacent to it is flipped to black.
-    for _ in 0..100 {
-        let mut next: HexGrid = HashMap::new();
-        for tile in tiles.values() {
-            let count = count_black_around_tile(tile.position, &tiles);
-            let p = tile.position;
-            // We are only inserting any tiles that qualify as black (and their immediate neighbours)
-            match (tile.colour.clone(), count) {
-                (Colours::White, 2) => {
-                    // Flip white tiles surrounded by exactly 2 black tiles
-                    let mut t = HexTile::new(p);
-                    t.flip();
-                    next.insert(p, t);
-                    insert_around_tile(p, &mut next);
-                }
-                (Colours::Black, _) if count > 0 && count < 3 => {
-                    // This tile stays black
-                    let mut t = HexTile::new(p);
-                    t.flip();
-                    next.insert(p, t);
-                    insert_around_tile(p, &mut next);
-                }
-                (_, _) => (),  // Do nothing - the tile would be white
-            }
+    let black_tiles = tiles.values().filter(|t| t.colour == Colours::Black).map(|t| t.position);
+    let mut floor = CellularAutomaton::sparse(HexTopology, Rule::parse("B2/S12").unwrap(), black_tiles);
+    for day in 0..100 {
+        if let Some(dir) = &frames_dir {
+            write_frame(dir, day, &floor);
         }
-        tiles = next;
+        floor.step();
     }
-    println!("Day 100: {}", count_black_tiles(&tiles));
+    if let Some(dir) = &frames_dir {
+        write_frame(dir, 100, &floor);
+        println!("Frames written to {}", dir);
+    }
+    println!("Day 100: {}", floor.live_count());
+}
+
+/// Write the floor on a given day to `<dir>/day_NNN.svg` and `<dir>/day_NNN.txt`
+///
+/// # Arguments
+///
+/// * `dir` the directory to write to (created if it doesn't exist)
+/// * `day` the day number used in the file names
+/// * `floor` the current state of the floor
+fn write_frame(dir: &str, day: usize, floor: &CellularAutomaton<HexTopology>) {
+    let tiles = black_tile_grid(floor.live_cells());
+    let path = Path::new(dir);
+    fs::create_dir_all(path).unwrap();
+    fs::write(path.join(format!("day_{:03}.svg", day)), render_svg(&tiles, 10.0, false)).unwrap();
+    fs::write(path.join(format!("day_{:03}.txt", day)), render_ascii(&tiles)).unwrap();
 }
 
 /// Read in lines of a file to a vector
@@ -82,58 +81,8 @@ fn read_in_lines(filename: &str) -> Vec<String> {
     let file = File::open(filename).unwrap();
     let reader = BufReader::new(file);
 
-    reader.lines().flatten().collect()
-
-}
+    reader.lines().map(|l| l.unwrap()).collect()
 
-/// Count the number of black tiles around a given HexTile
-/// 
-/// # Arguments
-/// 
-/// * `p` the point to look around
-/// * `tiles` HexGrid of tiles to check 
-fn count_black_around_tile(p: HexCoordinate, tiles: &HexGrid) -> u32 {
-    // Given position adjacent tiles are:
-    let adjacent = vec!((p.0  , p.1+1),     // e : (+0, +1)
-                        (p.0-1, p.1+1),     // se: (-1, +1)
-                        (p.0-1, p.1  ),     // sw: (-1, +0)
-                        (p.0  , p.1-1),     // w : (+0, -1)
-                        (p.0+1, p.1-1),     // nw: (+1, -1)
-                        (p.0+1, p.1  ));    // ne: (+1, +0)
-    let mut count = 0;
-    for point in adjacent {
-        if let Some(tile) = tiles.get(&point) {
-            match tile.colour {
-                Colours::White => (),
-                Colours::Black => {
-                    count += 1;
-                }
-            }
-        }
-    }
-    count
-}
-
-/// Insert white tiles around the given hex coordinate
-/// 
-/// # Arguments
-/// 
-/// * `p` the HexCoordinate to insert the tiles around
-/// * `tiles` the HexGrid to modify
-fn insert_around_tile(p: HexCoordinate, tiles: &mut HexGrid) {
-    // Given position adjacent tiles are:
-    let adjacent = vec!((p.0  , p.1+1),     // e : (+0, +1)
-                        (p.0-1, p.1+1),     // se: (-1, +1)
-                        (p.0-1, p.1  ),     // sw: (-1, +0)
-                        (p.0  , p.1-1),     // w : (+0, -1)
-                        (p.0+1, p.1-1),     // nw: (+1, -1)
-                        (p.0+1, p.1  ));    // ne: (+1, +0)
-
-    for point in adjacent {
-        if !tiles.contains_key(&point) {
-            tiles.insert(point, HexTile::new(point));
-        }
-    }
 }
 
 /// Counts the total number of black tiles in a HexGrid
@@ -0,0 +1,129 @@
+use std::fmt::Write;
+
+use crate::hexgrid::{Colours, Hex, HexGrid, HexTile};
+
+/// Create a grid containing a black tile at each of the given positions
+///
+/// # Arguments
+///
+/// * `positions` the coordinates of the black tiles
+pub fn black_tile_grid<I>(positions: I) -> HexGrid
+where
+    I: IntoIterator<Item = Hex>,
+{
+    positions
+        .into_iter()
+        .map(|p| {
+            let mut tile = HexTile::new(p);
+            tile.flip();
+            (p, tile)
+        })
+        .collect()
+}
+
+/// Render the tiles in a grid as an SVG image of pointy-top hexagons
+///
+/// # Arguments
+///
+/// * `tiles` the grid to draw (white tiles are drawn as outlines, missing tiles are not drawn)
+/// * `size` the distance from the centre of a hexagon to a corner
+/// * `label` whether to write the axial (r, c) coordinate in each tile
+///
+/// # Returns
+///
+/// * The SVG document as a string
+pub fn render_svg(tiles: &HexGrid, size: f64, label: bool) -> String {
+    let width = 3f64.sqrt() * size;
+    // Pixel centre of a hex - using the cube coordinate x (east) and z (south) axes
+    let centre = |h: Hex| -> (f64, f64) {
+        let (x, _, z) = h.to_cube();
+        (width * (x as f64 + z as f64 / 2.0), 1.5 * size * z as f64)
+    };
+
+    if tiles.is_empty() {
+        return "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 0 0\">\n</svg>\n".to_string();
+    }
+    let mut positions: Vec<&HexTile> = tiles.values().collect();
+    positions.sort_by_key(|t| t.position.to_offset().1);
+
+    let centres: Vec<(f64, f64)> = positions.iter().map(|t| centre(t.position)).collect();
+    let min_x = centres.iter().map(|c| c.0).fold(f64::INFINITY, f64::min);
+    let max_x = centres.iter().map(|c| c.0).fold(f64::NEG_INFINITY, f64::max);
+    let min_y = centres.iter().map(|c| c.1).fold(f64::INFINITY, f64::min);
+    let max_y = centres.iter().map(|c| c.1).fold(f64::NEG_INFINITY, f64::max);
+    let margin = size * 1.5;
+    let view_width = max_x - min_x + 2.0 * margin;
+    let view_height = max_y - min_y + 2.0 * margin;
+
+    let mut svg = String::new();
+    writeln!(svg, "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{:.2} {:.2} {:.2} {:.2}\">",
+             min_x - margin, min_y - margin, view_width, view_height).unwrap();
+    writeln!(svg, "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" fill=\"#ddd\"/>",
+             min_x - margin, min_y - margin, view_width, view_height).unwrap();
+    for (tile, (cx, cy)) in positions.iter().zip(centres) {
+        // Corners of a pointy-top hexagon are at 30 + 60*i degrees
+        let points: Vec<String> = (0..6)
+            .map(|i| {
+                let angle = (60.0 * i as f64 - 30.0).to_radians();
+                format!("{:.2},{:.2}", cx + size * angle.cos(), cy + size * angle.sin())
+            })
+            .collect();
+        let fill = match tile.colour {
+            Colours::Black => "black",
+            Colours::White => "white",
+        };
+        writeln!(svg, "<polygon points=\"{}\" fill=\"{}\" stroke=\"#888\" stroke-width=\"{:.2}\"/>",
+                 points.join(" "), fill, size / 20.0).unwrap();
+        if label {
+            let text_colour = match tile.colour {
+                Colours::Black => "white",
+                Colours::White => "black",
+            };
+            writeln!(svg, "<text x=\"{:.2}\" y=\"{:.2}\" font-size=\"{:.2}\" text-anchor=\"middle\" dominant-baseline=\"middle\" fill=\"{}\">{},{}</text>",
+                     cx, cy, size / 2.5, text_colour, tile.position.r, tile.position.c).unwrap();
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Render the tiles in a grid as text using "odd-r" offset rows - odd rows are indented by half a tile.
+/// Black tiles are drawn as '#' and white (or missing) tiles as '.'
+///
+/// # Arguments
+///
+/// * `tiles` the grid to draw
+///
+/// # Returns
+///
+/// * The map as a string with one line per row of tiles (north at the top)
+pub fn render_ascii(tiles: &HexGrid) -> String {
+    let offsets: Vec<(i32, i32)> = tiles.keys().map(|h| h.to_offset()).collect();
+    if offsets.is_empty() {
+        return String::new();
+    }
+    let min_col = offsets.iter().map(|o| o.0).min().unwrap();
+    let max_col = offsets.iter().map(|o| o.0).max().unwrap();
+    let min_row = offsets.iter().map(|o| o.1).min().unwrap();
+    let max_row = offsets.iter().map(|o| o.1).max().unwrap();
+
+    let mut map = String::new();
+    for row in min_row..=max_row {
+        if row & 1 == 1 {
+            map.push(' ');
+        }
+        for col in min_col..=max_col {
+            let hex = Hex::from_offset((col, row));
+            let ch = match tiles.get(&hex).map(|t| &t.colour) {
+                Some(Colours::Black) => '#',
+                _ => '.',
+            };
+            map.push(ch);
+            if col != max_col {
+                map.push(' ');
+            }
+        }
+        map.push('\n');
+    }
+    map
+}